@@ -0,0 +1,79 @@
+//! Core operations, per width. `SeqU16` and `SeqU32` are full width; `SeqU24`
+//! and the 14-bit type mask into wider storage, which takes different
+//! branches in `cmp`, `Add` and masking.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use seqnum::{SequenceInt, UInt};
+
+const N: usize = 1024;
+
+// xorshift64, so every run sees the same inputs
+fn values<T: UInt>() -> Vec<T> {
+    let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+    (0..N)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            T::truncate_from_u64(state)
+        })
+        .collect()
+}
+
+fn bench_width<T, const BITS: u8>(c: &mut Criterion, name: &str)
+where
+    T: UInt,
+{
+    let raw = values::<T>();
+    let seqs = raw
+        .iter()
+        .map(|&v| SequenceInt::<T, BITS>::from(v))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Elements(N as u64));
+
+    group.bench_function(BenchmarkId::new("cmp", BITS), |b| {
+        b.iter(|| {
+            seqs.windows(2)
+                .filter(|w| black_box(w[0]) < black_box(w[1]))
+                .count()
+        })
+    });
+    group.bench_function(BenchmarkId::new("inc", BITS), |b| {
+        b.iter(|| {
+            let mut seq = black_box(seqs[0]);
+            for _ in 0..N {
+                black_box(&mut seq).inc();
+            }
+            seq
+        })
+    });
+    group.bench_function(BenchmarkId::new("add", BITS), |b| {
+        b.iter(|| {
+            seqs.iter()
+                .zip(&raw)
+                .fold(black_box(seqs[0]), |acc, (&s, &v)| acc + s + v)
+        })
+    });
+    group.bench_function(BenchmarkId::new("mask", BITS), |b| {
+        b.iter(|| {
+            raw.iter()
+                .map(|&v| SequenceInt::<T, BITS>::from(black_box(v)).get())
+                .fold(T::ZERO, |acc, v| acc.wrapping_add(v))
+        })
+    });
+    group.finish();
+}
+
+fn bench_ops(c: &mut Criterion) {
+    bench_width::<u16, 16>(c, "SeqU16");
+    bench_width::<u32, 24>(c, "SeqU24");
+    bench_width::<u32, 32>(c, "SeqU32");
+    bench_width::<u16, 14>(c, "14-bit");
+}
+
+criterion_group!(benches, bench_ops);
+criterion_main!(benches);
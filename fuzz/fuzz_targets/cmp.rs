@@ -0,0 +1,32 @@
+#![no_main]
+
+use core::cmp::Ordering;
+
+use libfuzzer_sys::fuzz_target;
+use seqnum::{SeqU16, SeqU32, SeqU64};
+
+// RFC 1982 section 3.2 as written, with the undefined half-range case
+// resolved to `Greater` like the default `GreaterWins` policy.
+fn reference(a: u64, b: u64, bits: u32) -> Ordering {
+    let half = 1u64 << (bits - 1);
+    if a == b {
+        Ordering::Equal
+    } else if (a < b && b - a < half) || (a > b && a - b > half) {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+fuzz_target!(|data: (u64, u64)| {
+    let (a, b) = data;
+    assert_eq!(
+        SeqU16::from(a as u16).cmp(&SeqU16::from(b as u16)),
+        reference(a as u16 as u64, b as u16 as u64, 16)
+    );
+    assert_eq!(
+        SeqU32::from(a as u32).cmp(&SeqU32::from(b as u32)),
+        reference(a as u32 as u64, b as u32 as u64, 32)
+    );
+    assert_eq!(SeqU64::from(a).cmp(&SeqU64::from(b)), reference(a, b, 64));
+});
@@ -0,0 +1,59 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use seqnum::{SequenceInt, UInt};
+
+// Applies the operations encoded in `ops` to a sequence number built from
+// `raw`, checking after every step that the inner value stays within `BITS`.
+fn run<T, const BITS: u8>(raw: u64, ops: &[u8])
+where
+    T: UInt + TryFrom<u64>,
+{
+    let mask = u64::MAX >> (64 - BITS as u32);
+    let storage_mask = u64::MAX >> (64 - T::BITS);
+    let to_t = |v: u64| match T::try_from(v & storage_mask) {
+        Ok(t) => t,
+        Err(_) => unreachable!(),
+    };
+    let check = |s: SequenceInt<T, BITS>| {
+        let v = s.0.to_u64();
+        assert_eq!(v & mask, v, "{s:?} escaped {BITS} bits");
+    };
+
+    let mut s = SequenceInt::<T, BITS>::from(to_t(raw));
+    check(s);
+    for chunk in ops.chunks(9) {
+        let (&op, arg) = chunk.split_first().unwrap();
+        let mut buf = [0u8; 8];
+        buf[..arg.len()].copy_from_slice(arg);
+        let arg = to_t(u64::from_le_bytes(buf));
+        match op % 5 {
+            0 => s.inc(),
+            1 => s.dec(),
+            2 => s = s + arg,
+            3 => s = s - arg,
+            _ => s = SequenceInt::from(arg),
+        }
+        check(s);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&width, rest)) = data.split_first() else {
+        return;
+    };
+    if rest.len() < 8 {
+        return;
+    }
+    let (raw, ops) = rest.split_at(8);
+    let raw = u64::from_le_bytes(raw.try_into().unwrap());
+    match width % 7 {
+        0 => run::<u8, 8>(raw, ops),
+        1 => run::<u16, 16>(raw, ops),
+        2 => run::<u32, 24>(raw, ops),
+        3 => run::<u32, 32>(raw, ops),
+        4 => run::<u64, 64>(raw, ops),
+        5 => run::<u32, 14>(raw, ops),
+        _ => run::<u32, 20>(raw, ops),
+    }
+});
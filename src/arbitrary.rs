@@ -0,0 +1,27 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{SequenceInt, TieBreak, UInt};
+
+// Masks like `From<T>`, so every generated value is a valid member of the
+// sequence space.
+impl<'a, T, const BITS: u8, P> Arbitrary<'a> for SequenceInt<T, BITS, P>
+where
+    T: UInt + Arbitrary<'a>,
+    P: TieBreak,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        T::arbitrary(u).map(Self::from)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        T::size_hint(depth)
+    }
+}
+
+#[test]
+fn test_arbitrary_masks() {
+    let bytes = [0xff; 32];
+    let mut u = Unstructured::new(&bytes);
+    let s = SequenceInt::<u32, 14>::arbitrary(&mut u).unwrap();
+    assert_eq!(s.0, 16_383);
+}
@@ -0,0 +1,191 @@
+use core::array::TryFromSliceError;
+use core::marker::PhantomData;
+
+use crate::{SequenceInt, TieBreak, UInt};
+
+// Big-endian (network order) conversions for the full-width aliases, where
+// the sequence number is exactly the storage integer
+macro_rules! impl_full_width_bytes {
+    ($t:ty, $bits:literal, $n:literal) => {
+        impl<P: TieBreak> From<[u8; $n]> for SequenceInt<$t, $bits, P> {
+            #[inline]
+            fn from(bytes: [u8; $n]) -> Self {
+                Self(<$t>::from_be_bytes(bytes), PhantomData)
+            }
+        }
+
+        impl<P: TieBreak> TryFrom<&[u8]> for SequenceInt<$t, $bits, P> {
+            type Error = TryFromSliceError;
+
+            /// Decodes a big-endian slice, failing unless it is exactly
+            #[doc = concat!($n, " bytes long.")]
+            #[inline]
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                <[u8; $n]>::try_from(bytes).map(Self::from)
+            }
+        }
+    };
+}
+
+// Any width: the value is encoded in the fewest whole bytes that hold `BITS`
+// bits, e.g. 3 for a 24-bit sequence number. `N` is that byte count, which
+// stable Rust can't compute from `BITS` in a signature, so it is checked at
+// build time instead.
+impl<T, const BITS: u8, P> SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    const fn check_byte_len<const N: usize>() {
+        assert!(
+            N == (BITS as usize).div_ceil(8),
+            "N must be BITS.div_ceil(8)"
+        );
+    }
+
+    /// The value as `ceil(BITS / 8)` big-endian bytes. Fails to build for
+    /// any other `N`.
+    #[must_use]
+    #[inline]
+    pub fn to_be_bytes<const N: usize>(self) -> [u8; N] {
+        const { Self::check_byte_len::<N>() };
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.get().to_u64().to_be_bytes()[8 - N..]);
+        bytes
+    }
+
+    /// The value as `ceil(BITS / 8)` little-endian bytes. Fails to build for
+    /// any other `N`.
+    #[must_use]
+    #[inline]
+    pub fn to_le_bytes<const N: usize>(self) -> [u8; N] {
+        const { Self::check_byte_len::<N>() };
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&self.get().to_u64().to_le_bytes()[..N]);
+        bytes
+    }
+
+    /// Decodes `ceil(BITS / 8)` big-endian bytes, masking off any bits above
+    /// `BITS` like `From<T>`. Fails to build for any other `N`.
+    #[must_use]
+    #[inline]
+    pub fn from_be_bytes<const N: usize>(bytes: [u8; N]) -> Self {
+        const { Self::check_byte_len::<N>() };
+        let mut buf = [0; 8];
+        buf[8 - N..].copy_from_slice(&bytes);
+        Self::from(T::truncate_from_u64(u64::from_be_bytes(buf)))
+    }
+
+    /// Decodes `ceil(BITS / 8)` little-endian bytes, masking off any bits
+    /// above `BITS` like `From<T>`. Fails to build for any other `N`.
+    #[must_use]
+    #[inline]
+    pub fn from_le_bytes<const N: usize>(bytes: [u8; N]) -> Self {
+        const { Self::check_byte_len::<N>() };
+        let mut buf = [0; 8];
+        buf[..N].copy_from_slice(&bytes);
+        Self::from(T::truncate_from_u64(u64::from_le_bytes(buf)))
+    }
+
+    /// The value as `ceil(BITS / 8)` bytes in the target's native order, for
+    /// same-host IPC. Fails to build for any other `N`.
+    #[must_use]
+    #[inline]
+    pub fn to_ne_bytes<const N: usize>(self) -> [u8; N] {
+        if cfg!(target_endian = "big") {
+            self.to_be_bytes()
+        } else {
+            self.to_le_bytes()
+        }
+    }
+
+    /// Decodes `ceil(BITS / 8)` bytes in the target's native order, masking
+    /// off any bits above `BITS` like `From<T>`. Fails to build for any other
+    /// `N`.
+    #[must_use]
+    #[inline]
+    pub fn from_ne_bytes<const N: usize>(bytes: [u8; N]) -> Self {
+        if cfg!(target_endian = "big") {
+            Self::from_be_bytes(bytes)
+        } else {
+            Self::from_le_bytes(bytes)
+        }
+    }
+}
+
+impl_full_width_bytes!(u8, 8, 1);
+impl_full_width_bytes!(u16, 16, 2);
+impl_full_width_bytes!(u32, 32, 4);
+impl_full_width_bytes!(u64, 64, 8);
+
+#[test]
+fn test_full_width_bytes() {
+    use crate::{SeqU8, SeqU16, SeqU32, SeqU64};
+
+    assert_eq!(
+        SeqU32::from([0x12, 0x34, 0x56, 0x78]),
+        SeqU32::from(0x1234_5678)
+    );
+    assert_eq!(SeqU16::from([0xff, 0xfe]), SeqU16::from(0xfffe));
+    assert_eq!(SeqU8::from([7]), SeqU8::from(7));
+    assert_eq!(
+        SeqU64::from([1, 2, 3, 4, 5, 6, 7, 8]),
+        SeqU64::from(0x0102_0304_0506_0708)
+    );
+
+    let packet = [0xde, 0xad, 0xbe, 0xef, 0x00];
+    assert_eq!(
+        SeqU32::try_from(&packet[..4]).unwrap(),
+        SeqU32::from(0xdead_beef)
+    );
+    assert!(SeqU32::try_from(&packet[..]).is_err());
+    assert!(SeqU32::try_from(&packet[..3]).is_err());
+    assert_eq!(
+        SeqU16::try_from(&packet[2..4]).unwrap(),
+        SeqU16::from(0xbeef)
+    );
+}
+
+#[test]
+fn test_sized_bytes() {
+    use crate::{SeqU16, SeqU24, SeqU64};
+
+    let s = SeqU24::from(0x12_3456);
+    assert_eq!(s.to_be_bytes(), [0x12, 0x34, 0x56]);
+    assert_eq!(s.to_le_bytes(), [0x56, 0x34, 0x12]);
+    assert_eq!(SeqU24::from_be_bytes([0x12, 0x34, 0x56]), s);
+    assert_eq!(SeqU24::from_le_bytes([0x56, 0x34, 0x12]), s);
+
+    // 14 bits take two bytes, and the top two bits are masked off on decode
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(0x3abc).to_be_bytes(), [0x3a, 0xbc]);
+    assert_eq!(S14::from_be_bytes([0xff, 0xff]), S14::from(0x3fff));
+    assert_eq!(S14::from_le_bytes([0x01, 0xc0]), S14::from(1));
+
+    type S9 = SequenceInt<u16, 9>;
+    assert_eq!(S9::from(0x1ff).to_le_bytes(), [0xff, 0x01]);
+
+    assert_eq!(SeqU16::from(0xbeef).to_be_bytes(), 0xbeef_u16.to_be_bytes());
+    let v = SeqU64::from(0x0102_0304_0506_0708);
+    assert_eq!(SeqU64::from_le_bytes(v.to_le_bytes::<8>()), v);
+}
+
+#[test]
+fn test_ne_bytes() {
+    use crate::{SeqU24, SeqU32};
+
+    let s = SeqU32::from(0xdead_beef);
+    assert_eq!(s.to_ne_bytes(), 0xdead_beef_u32.to_ne_bytes());
+    assert_eq!(SeqU32::from_ne_bytes(s.to_ne_bytes::<4>()), s);
+
+    let s = SeqU24::from(0xab_cdef);
+    let bytes = s.to_ne_bytes::<3>();
+    let expected = if cfg!(target_endian = "big") {
+        [0xab, 0xcd, 0xef]
+    } else {
+        [0xef, 0xcd, 0xab]
+    };
+    assert_eq!(bytes, expected);
+    assert_eq!(SeqU24::from_ne_bytes(bytes), s);
+    assert_eq!(SeqU24::from_ne_bytes([0xff; 3]), SeqU24::from(0xff_ffff));
+}
@@ -0,0 +1,331 @@
+use core::fmt;
+use core::iter::Sum;
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::{SequenceInt, TieBreak, UInt};
+
+/// Signed offset between two sequence numbers, as opposed to a point in the
+/// sequence space.
+///
+/// `a - b` on two [`SequenceInt`]s gives the delta from `b` to `a`, and adding
+/// it back moves a sequence number by that many steps, so `b + (a - b) == a`.
+/// The storage type `T` and width `BITS` keep deltas from different sequence
+/// spaces apart.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SeqDelta<T, const BITS: u8>
+where
+    T: UInt,
+{
+    delta: i64,
+    _storage: PhantomData<T>,
+}
+
+impl<T, const BITS: u8> SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    /// A delta of `delta` steps, saturated like the arithmetic operators, so
+    /// it is always one that subtracting two sequence numbers could give.
+    #[must_use]
+    #[inline]
+    pub const fn new(delta: i64) -> Self {
+        Self::saturating(delta as i128)
+    }
+
+    /// The number of steps, negative for a move backward.
+    #[must_use]
+    #[inline]
+    pub const fn get(self) -> i64 {
+        self.delta
+    }
+
+    /// Clamps to `[-half, half)` of the `BITS`-wide sequence space, the range
+    /// a difference between two of its sequence numbers can take.
+    const fn saturating(delta: i128) -> Self {
+        let () = SequenceInt::<T, BITS>::VALID_WIDTH;
+        let half = 1i128 << (BITS - 1);
+        let delta = if delta < -half {
+            -half
+        } else if delta >= half {
+            half - 1
+        } else {
+            delta
+        };
+        Self {
+            delta: delta as i64,
+            _storage: PhantomData,
+        }
+    }
+}
+
+impl<T, const BITS: u8> Neg for SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::saturating(-(self.delta as i128))
+    }
+}
+
+impl<T, const BITS: u8> Add for SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::saturating(self.delta as i128 + rhs.delta as i128)
+    }
+}
+
+impl<T, const BITS: u8> Sub for SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::saturating(self.delta as i128 - rhs.delta as i128)
+    }
+}
+
+// Totals exactly and saturates once at the end, so the result doesn't depend
+// on the order of the terms the way repeated saturating `+` would.
+impl<T, const BITS: u8> Sum for SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self::saturating(iter.map(|d| d.delta as i128).sum())
+    }
+}
+
+impl<'a, T, const BITS: u8> Sum<&'a SeqDelta<T, BITS>> for SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T, const BITS: u8> Mul<i64> for SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self::saturating(self.delta as i128 * rhs as i128)
+    }
+}
+
+impl<T, const BITS: u8> fmt::Debug for SeqDelta<T, BITS>
+where
+    T: UInt,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SeqDelta").field(&self.delta).finish()
+    }
+}
+
+// Moves forward for a positive delta and backward for a negative one; the
+// two's complement truncation to `T` makes the wrapping add do both.
+impl<T, const BITS: u8, P> Add<SeqDelta<T, BITS>> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: SeqDelta<T, BITS>) -> Self::Output {
+        self + T::truncate_from_u64(rhs.delta as u64)
+    }
+}
+
+impl<T, const BITS: u8, P> Sub<SeqDelta<T, BITS>> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: SeqDelta<T, BITS>) -> Self::Output {
+        self - T::truncate_from_u64(rhs.delta as u64)
+    }
+}
+
+// In-place forms of the above, with the same offset semantics: `seq += d`
+// moves `seq` by `d` steps.
+impl<T, const BITS: u8, P> AddAssign<SeqDelta<T, BITS>> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: SeqDelta<T, BITS>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T, const BITS: u8, P> SubAssign<SeqDelta<T, BITS>> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: SeqDelta<T, BITS>) {
+        *self = *self - rhs;
+    }
+}
+
+#[test]
+fn test_seq_delta() {
+    use crate::{SeqU16, SeqU64};
+
+    let d = SeqDelta::<u16, 16>::new(10);
+    assert_eq!(SeqU16::from(65530) + d, SeqU16::from(4));
+    assert_eq!(SeqU16::from(4) - SeqU16::from(65530), d);
+    assert_eq!(SeqU16::from(4) + SeqDelta::new(-10), SeqU16::from(65530));
+    assert_eq!(SeqU16::from(65530) - SeqU16::from(4), SeqDelta::new(-10));
+    assert_eq!(format!("{d:?}"), "SeqDelta(10)");
+
+    type S14 = SequenceInt<u32, 14>;
+    let (a, b) = (S14::from(16_380), S14::from(7));
+    assert_eq!((b - a).get(), 11);
+    assert_eq!(a + (b - a), b);
+    assert_eq!(b + (a - b), a);
+
+    let (a, b) = (SeqU64::from(u64::MAX - 1), SeqU64::from(3));
+    assert_eq!(b - a, SeqDelta::new(5));
+    assert_eq!(a + (b - a), b);
+    assert_eq!(b + (a - b), a);
+}
+
+#[test]
+fn test_seq_delta_arithmetic() {
+    use crate::SeqU16;
+
+    let (a, b) = (SeqDelta::<u16, 16>::new(10), SeqDelta::<u16, 16>::new(-3));
+    assert_eq!(-a, SeqDelta::new(-10));
+    assert_eq!(a + b, SeqDelta::new(7));
+    assert_eq!(a - b, SeqDelta::new(13));
+    assert_eq!(b * 4, SeqDelta::new(-12));
+    assert_eq!(SeqU16::from(65530) + (a + b), SeqU16::from(1));
+
+    // saturates at the half-range of the storage type
+    let max = SeqDelta::<u16, 16>::new(32_767);
+    let min = SeqDelta::<u16, 16>::new(-32_768);
+    assert_eq!(max + a, max);
+    assert_eq!(min - a, min);
+    assert_eq!(-min, max);
+    assert_eq!(a * 10_000, max);
+    assert_eq!(
+        SeqDelta::<u64, 64>::new(i64::MIN) + SeqDelta::new(-1),
+        SeqDelta::new(i64::MIN)
+    );
+    assert_eq!(-SeqDelta::<u64, 64>::new(i64::MIN), SeqDelta::new(i64::MAX));
+}
+
+#[test]
+fn test_seq_delta_narrow() {
+    // saturates at the half-range of the sequence space, not of `u32`
+    type D14 = SeqDelta<u32, 14>;
+    let (max, min) = (D14::new(8191), D14::new(-8192));
+    assert_eq!(max + D14::new(1), max);
+    assert_eq!(min - D14::new(1), min);
+    assert_eq!(-min, max);
+    assert_eq!(D14::new(100) * 1000, max);
+    assert_eq!(D14::new(-100) * 1000, min);
+    assert_eq!([D14::new(5000); 2].iter().sum::<D14>(), max);
+    assert_eq!(
+        [D14::new(5000), D14::new(5000), D14::new(-5000)]
+            .iter()
+            .sum::<D14>(),
+        D14::new(5000)
+    );
+
+    // `new` saturates too, so it can't build a delta no subtraction gives
+    assert_eq!(SeqDelta::<u8, 8>::new(1000).get(), 127);
+    assert_eq!(SeqDelta::<u8, 8>::new(-1000).get(), -128);
+    assert_eq!(D14::new(i64::MAX), max);
+
+    let (max, min) = (SeqDelta::<u8, 3>::new(3), SeqDelta::<u8, 3>::new(-4));
+    assert_eq!(max + max, max);
+    assert_eq!(min + min, min);
+    assert_eq!(-min, max);
+}
+
+#[test]
+fn test_sub_seq_delta() {
+    use crate::SeqU16;
+
+    assert_eq!(SeqU16::from(4) - SeqDelta::new(10), SeqU16::from(65530));
+    assert_eq!(SeqU16::from(65530) - SeqDelta::new(-10), SeqU16::from(4));
+
+    fn check<const BITS: u8>(state: &mut u64) {
+        for _ in 0..1000 {
+            let seq = SequenceInt::<u32, BITS>::from(crate::next_rand(state) as u32);
+            let d = SeqDelta::new((crate::next_rand(state) % 2001) as i64 - 1000);
+            assert_eq!((seq + d) - d, seq);
+            assert_eq!((seq - d) + d, seq);
+        }
+    }
+
+    let mut state = 0xde17a_u64;
+    check::<32>(&mut state);
+    check::<24>(&mut state);
+    check::<14>(&mut state);
+}
+
+#[test]
+fn test_seq_delta_assign() {
+    type S14 = SequenceInt<u32, 14>;
+
+    let mut state = 0xa55_u64;
+    for _ in 0..1000 {
+        let seq = S14::from(crate::next_rand(&mut state) as u32);
+        let d = SeqDelta::new((crate::next_rand(&mut state) % 40_001) as i64 - 20_000);
+        let mut x = seq;
+        x += d;
+        assert_eq!(x, seq + d);
+        x -= d;
+        assert_eq!(x, seq);
+        x -= d;
+        assert_eq!(x, seq - d);
+    }
+}
+
+#[test]
+fn test_seq_delta_sum() {
+    let deltas = [100, -30, 7, -2].map(SeqDelta::<u16, 16>::new).to_vec();
+    assert_eq!(deltas.iter().sum::<SeqDelta<u16, 16>>(), SeqDelta::new(75));
+    assert_eq!(
+        deltas.into_iter().sum::<SeqDelta<u16, 16>>(),
+        SeqDelta::new(75)
+    );
+    assert_eq!(
+        core::iter::empty::<SeqDelta<u16, 16>>().sum::<SeqDelta<u16, 16>>(),
+        SeqDelta::new(0)
+    );
+
+    let big = [30_000, 30_000, -30_000].map(SeqDelta::<u16, 16>::new);
+    assert_eq!(big.iter().sum::<SeqDelta<u16, 16>>(), SeqDelta::new(30_000));
+    assert_eq!(
+        big[..2].iter().sum::<SeqDelta<u16, 16>>(),
+        SeqDelta::new(32_767)
+    );
+    let low = [-30_000; 3].map(SeqDelta::<u16, 16>::new);
+    assert_eq!(
+        low.iter().sum::<SeqDelta<u16, 16>>(),
+        SeqDelta::new(-32_768)
+    );
+    let huge = [i64::MAX; 4].map(SeqDelta::<u64, 64>::new);
+    assert_eq!(
+        huge.iter().sum::<SeqDelta<u64, 64>>(),
+        SeqDelta::new(i64::MAX)
+    );
+}
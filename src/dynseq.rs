@@ -0,0 +1,168 @@
+use core::cmp::Ordering;
+use core::ops::{Add, Sub};
+
+use crate::{InvalidWidth, SequenceInt, TieBreak, UInt};
+
+/// Sequence number whose width is only known at runtime, e.g. negotiated by a
+/// protocol.
+///
+/// Behaves like [`SequenceInt`](crate::SequenceInt), but stores `bits` next to
+/// the value instead of in the type. Values of different widths are never
+/// equal and don't compare.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DynSeq {
+    value: u64,
+    bits: u8,
+}
+
+impl DynSeq {
+    /// Creates a `bits`-wide sequence number, masking `value` into it.
+    ///
+    /// Fails unless `bits` is in `1..=64`.
+    pub fn new(value: u64, bits: u8) -> Result<Self, InvalidWidth> {
+        if !(1..=64).contains(&bits) {
+            return Err(InvalidWidth { bits });
+        }
+        Ok(Self { value: 0, bits }.with_masked(value))
+    }
+
+    /// [`new`](Self::new) from the parts of a
+    /// [`SequenceInt::into_parts`].
+    pub fn from_parts<T: UInt>(value: T, bits: u8) -> Result<Self, InvalidWidth> {
+        Self::new(value.to_u64(), bits)
+    }
+
+    /// Converts back to a fixed-width sequence number, or `None` if `BITS`
+    /// isn't this value's width.
+    #[must_use]
+    pub fn into_seq<T, const BITS: u8, P>(self) -> Option<SequenceInt<T, BITS, P>>
+    where
+        T: UInt,
+        P: TieBreak,
+    {
+        (self.bits == BITS).then(|| SequenceInt::from(T::truncate_from_u64(self.value)))
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn bits(self) -> u8 {
+        self.bits
+    }
+
+    #[inline]
+    fn mask(self) -> u64 {
+        u64::MAX >> (64 - self.bits as u32)
+    }
+
+    #[inline]
+    fn with_masked(self, value: u64) -> Self {
+        Self {
+            value: value & self.mask(),
+            bits: self.bits,
+        }
+    }
+
+    #[inline]
+    fn half_range(self) -> u64 {
+        1 << (self.bits - 1)
+    }
+
+    pub fn inc(&mut self) {
+        *self = *self + 1;
+    }
+    pub fn dec(&mut self) {
+        *self = *self - 1;
+    }
+}
+
+impl PartialOrd for DynSeq {
+    /// RFC 1982 ordering; `None` if the widths differ.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.bits != other.bits {
+            return None;
+        }
+        let diff = other.value.wrapping_sub(self.value) & self.mask();
+        Some(if diff == 0 {
+            Ordering::Equal
+        } else if diff < self.half_range() {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        })
+    }
+}
+
+impl Add<u64> for DynSeq {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: u64) -> Self::Output {
+        self.with_masked(self.value.wrapping_add(rhs))
+    }
+}
+
+impl Sub<u64> for DynSeq {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: u64) -> Self::Output {
+        self.with_masked(self.value.wrapping_sub(rhs))
+    }
+}
+
+#[test]
+fn test_dyn_seq() {
+    let seq = |v, bits| DynSeq::new(v, bits).unwrap();
+
+    assert_eq!(DynSeq::new(1, 0), Err(InvalidWidth { bits: 0 }));
+    assert_eq!(DynSeq::new(1, 65), Err(InvalidWidth { bits: 65 }));
+
+    for bits in [16u8, 24, 14, 64] {
+        let max = u64::MAX >> (64 - bits as u32);
+        assert!(seq(1000, bits) > seq(999, bits));
+        assert!(seq(max - 5, bits) < seq(10, bits));
+        assert_eq!(seq(7, bits), seq(7, bits));
+        assert_eq!(seq(max, bits) + 2, seq(1, bits));
+        assert_eq!(seq(1, bits) - 2, seq(max, bits));
+
+        let mut x = seq(max - 1, bits);
+        x.inc();
+        assert_eq!(x.value(), max);
+        x.inc();
+        assert_eq!(x.value(), 0);
+        x.dec();
+        assert_eq!(x.value(), max);
+    }
+
+    assert_eq!(seq(16_777_226, 24), seq(10, 24));
+    assert_eq!(seq(16_777_226, 24).bits(), 24);
+    assert!(seq(16_777_206, 24) < seq(16_777_208, 24));
+    assert!(seq(16_777_206, 24) < seq(10, 24));
+    assert!(seq(1000, 16) < seq(33000, 16));
+    assert!(seq(1000, 16) > seq(34000, 16));
+
+    assert_ne!(seq(1, 14), seq(1, 24));
+    assert_eq!(seq(1, 14).partial_cmp(&seq(2, 24)), None);
+}
+
+#[test]
+fn test_parts() {
+    use crate::SeqU24;
+
+    let (a, b) = (SeqU24::from(16_777_200), SeqU24::from(20));
+    assert_eq!(a.into_parts(), (16_777_200, 24));
+    let (da, db) = (
+        DynSeq::from_parts(a.into_parts().0, 24).unwrap(),
+        DynSeq::from_parts(b.into_parts().0, b.into_parts().1).unwrap(),
+    );
+    assert_eq!(da.partial_cmp(&db), Some(a.cmp(&b)));
+    assert_eq!(db.partial_cmp(&da), Some(b.cmp(&a)));
+    assert_eq!(da.into_seq(), Some(a));
+    assert_eq!(db.into_seq::<u32, 24, crate::GreaterWins>(), Some(b));
+    assert_eq!(da.into_seq::<u32, 16, crate::GreaterWins>(), None);
+    assert_eq!(DynSeq::from_parts(5u8, 0), Err(InvalidWidth { bits: 0 }));
+}
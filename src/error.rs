@@ -0,0 +1,88 @@
+use core::fmt;
+
+/// Error returned by strict constructors and parsers when a value doesn't fit
+/// in the sequence space.
+///
+/// `value` is a `u128` so that parsed input too large for any storage type
+/// can still be reported; `storage_bits` is the width of the storage type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub value: u128,
+    pub bits: u8,
+    pub storage_bits: u32,
+}
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is out of range for {} bits", self.value, self.bits)
+    }
+}
+
+impl core::error::Error for OutOfRange {}
+
+/// Error returned when a runtime bit width is outside `1..=64`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidWidth {
+    pub bits: u8,
+}
+
+impl fmt::Display for InvalidWidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid sequence width {}, must be 1..=64", self.bits)
+    }
+}
+
+impl core::error::Error for InvalidWidth {}
+
+/// Error returned by window-checked comparisons when two values are further
+/// apart than the window allows, e.g. because one of them is corrupt.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OutOfWindow {
+    pub distance: u64,
+    pub window: u64,
+}
+
+impl fmt::Display for OutOfWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "values are {} apart, beyond the window of {}",
+            self.distance, self.window
+        )
+    }
+}
+
+impl core::error::Error for OutOfWindow {}
+
+/// Error returned when building a range whose length exceeds a caller-given
+/// limit, e.g. from a forged or corrupt end point.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GapTooLarge {
+    pub len: u64,
+    pub max_len: u64,
+}
+
+impl fmt::Display for GapTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "range of {} values exceeds the limit of {}",
+            self.len, self.max_len
+        )
+    }
+}
+
+impl core::error::Error for GapTooLarge {}
+
+/// Error returned by strict comparisons when two values are exactly half the
+/// sequence space apart, where RFC 1982 leaves their order undefined.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ambiguous;
+
+impl fmt::Display for Ambiguous {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("values are exactly half the sequence space apart")
+    }
+}
+
+impl core::error::Error for Ambiguous {}
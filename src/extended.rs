@@ -0,0 +1,199 @@
+use core::marker::PhantomData;
+
+use crate::{GreaterWins, SequenceInt, TieBreak, UInt};
+
+/// Reconstructs a monotonic 64-bit index from a stream of wrapping sequence
+/// numbers, like RTP's extended sequence number.
+///
+/// Each observation is placed at the index nearest to the previous one, so
+/// consecutive observations must be less than half the sequence space apart.
+#[derive(Clone, Debug, Default)]
+pub struct ExtendedCounter<T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    last: Option<u64>,
+    prev: Option<u64>,
+    _seq: PhantomData<SequenceInt<T, BITS, P>>,
+}
+
+impl<T, const BITS: u8, P> ExtendedCounter<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    pub fn new() -> Self {
+        Self {
+            last: None,
+            prev: None,
+            _seq: PhantomData,
+        }
+    }
+
+    /// Index of the latest observation, if any.
+    #[must_use]
+    pub fn index(&self) -> Option<u64> {
+        self.last
+    }
+
+    /// Records `seq` and returns its extended index.
+    ///
+    /// The first observation's index is its raw value. Later ones step
+    /// forward or back from the previous index by their signed distance,
+    /// stopping at zero.
+    pub fn update(&mut self, seq: SequenceInt<T, BITS, P>) -> u64 {
+        let index = match self.last {
+            Some(last) => {
+                let last_seq = SequenceInt::<T, BITS, P>::from(T::truncate_from_u64(last));
+                last.saturating_add_signed(last_seq.distance(seq))
+            }
+            None => seq.0.to_u64(),
+        };
+        self.prev = self.last.replace(index);
+        index
+    }
+
+    /// Increments between the last two observations' indices, wraps included.
+    ///
+    /// Zero until there are two observations, or when the latest one went
+    /// backward (a late arrival).
+    #[must_use]
+    pub fn delta_since_last(&self) -> u64 {
+        match (self.prev, self.last) {
+            (Some(prev), Some(last)) => last.saturating_sub(prev),
+            _ => 0,
+        }
+    }
+}
+
+/// Sender-side counterpart to [`ExtendedCounter`]: a sequence number that
+/// counts how many times it has wrapped as it advances, so its total position
+/// is known exactly.
+#[derive(Clone, Debug)]
+pub struct WrappingCounter<T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    seq: SequenceInt<T, BITS, P>,
+    wraps: u64,
+}
+
+impl<T, const BITS: u8, P> WrappingCounter<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    /// Creates a counter at `start`, with no wraps yet.
+    pub fn new(start: SequenceInt<T, BITS, P>) -> Self {
+        Self {
+            seq: start,
+            wraps: 0,
+        }
+    }
+
+    /// The current sequence number.
+    #[must_use]
+    pub fn seq(&self) -> SequenceInt<T, BITS, P> {
+        self.seq
+    }
+
+    /// Number of times the counter has wrapped past the top of the space.
+    #[must_use]
+    pub fn wraps(&self) -> u64 {
+        self.wraps
+    }
+
+    /// Total position, `wraps * 2^BITS + seq`, truncated to 64 bits.
+    ///
+    /// This loses information once the total reaches `2^64`, i.e. after
+    /// `2^(64 - BITS)` wraps: the position then starts again from zero while
+    /// [`wraps`](Self::wraps) keeps counting. For a 64-bit counter it is
+    /// always just `seq`.
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        let base = self.wraps.checked_shl(BITS as u32).unwrap_or(0);
+        base.wrapping_add(self.seq.get().to_u64())
+    }
+
+    /// Advances by `by` steps and returns the new [`position`](Self::position).
+    pub fn advance(&mut self, by: T) -> u64 {
+        // whole laps first, for storage wider than `BITS`, then the rest
+        let laps = (by.to_u128() >> BITS) as u64;
+        let (seq, wrapped) = self
+            .seq
+            .overflowing_add(SequenceInt::<T, BITS, P>::from(by).get());
+        self.seq = seq;
+        self.wraps = self.wraps.wrapping_add(laps + wrapped as u64);
+        self.position()
+    }
+}
+
+#[test]
+fn test_extended_counter() {
+    use crate::SeqU16;
+
+    let mut ext = ExtendedCounter::<u16, 16>::new();
+    assert_eq!(ext.delta_since_last(), 0);
+    assert_eq!(ext.update(SeqU16::from(65530)), 65530);
+    assert_eq!(ext.delta_since_last(), 0);
+    assert_eq!(ext.update(SeqU16::from(65535)), 65535);
+    assert_eq!(ext.delta_since_last(), 5);
+    assert_eq!(ext.update(SeqU16::from(3)), 65539);
+    assert_eq!(ext.delta_since_last(), 4);
+    assert_eq!(ext.update(SeqU16::from(1)), 65537);
+    assert_eq!(ext.delta_since_last(), 0);
+    assert_eq!(ext.index(), Some(65537));
+
+    // an 8-bit stream wrapping many times, in jumps of up to 127
+    let mut ext = ExtendedCounter::<u8, 8>::new();
+    let mut expected = 0u64;
+    ext.update(SequenceInt::from(0));
+    for step in [127u64, 100, 127, 1, 90, 127, 127, 3] {
+        expected += step;
+        assert_eq!(ext.update(SequenceInt::from(expected as u8)), expected);
+        assert_eq!(ext.delta_since_last(), step);
+    }
+    assert_eq!(ext.index(), Some(702));
+}
+
+#[test]
+fn test_wrapping_counter() {
+    use crate::SeqU8;
+
+    let mut c = WrappingCounter::<u8, 8>::new(SeqU8::from(250));
+    assert_eq!(c.position(), 250);
+    assert_eq!(c.advance(5), 255);
+    assert_eq!(c.advance(1), 256);
+    assert_eq!((c.seq(), c.wraps()), (SeqU8::from(0), 1));
+    let mut expected = 256u64;
+    for by in [255u8, 1, 200, 200, 56, 0, 255] {
+        expected += by as u64;
+        assert_eq!(c.advance(by), expected);
+    }
+    assert_eq!(c.wraps(), expected / 256);
+
+    // 14 bits in u32 storage, stepping several laps at once
+    let mut c = WrappingCounter::<u32, 14>::new(SequenceInt::from(16_000));
+    assert_eq!(c.advance(100_000), 116_000);
+    assert_eq!(c.wraps(), 116_000 / 16_384);
+    assert_eq!(c.advance(16_384), 132_384);
+    assert_eq!(c.advance(3 * 16_384 + 5), 181_541);
+    assert_eq!(c.seq().get(), 181_541 % 16_384);
+}
+
+#[test]
+fn test_wrapping_counter_jump() {
+    // one step covering several wraps, landing just short of 2^64
+    let mut c = WrappingCounter::<u64, 60>::new(SequenceInt::from(0));
+    assert_eq!(c.advance(u64::MAX), u64::MAX);
+    assert_eq!((c.seq().get(), c.wraps()), ((1 << 60) - 1, 15));
+    // past 2^64 the position truncates, but the wrap count doesn't
+    assert_eq!(c.advance(3), 2);
+    assert_eq!((c.seq().get(), c.wraps()), (2, 16));
+
+    let mut c = WrappingCounter::<u64, 64>::new(SequenceInt::from(10));
+    assert_eq!(c.advance(u64::MAX), 9);
+    assert_eq!(c.wraps(), 1);
+}
@@ -1,6 +1,55 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod bytes;
+mod delta;
+mod dynseq;
+mod error;
+mod extended;
+mod parse;
+mod range;
+#[cfg(feature = "alloc")]
+mod range_set;
+mod reorder;
+#[cfg(feature = "alloc")]
+mod ring;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod stats;
+#[cfg(any(feature = "testing", test))]
+pub mod testing;
+mod tracker;
+mod window;
+
+pub use delta::SeqDelta;
+pub use dynseq::DynSeq;
+pub use error::{Ambiguous, GapTooLarge, InvalidWidth, OutOfRange, OutOfWindow};
+pub use extended::{ExtendedCounter, WrappingCounter};
+pub use parse::ParseSeqError;
+pub use range::{SeqRange, SeqRangeIter};
+#[cfg(feature = "smallvec")]
+pub use range_set::SmallSeqRangeSet;
+#[cfg(feature = "alloc")]
+pub use range_set::{RangeStorage, SeqRangeSet, SeqRangeSetIter};
+pub use reorder::{FixedReorderBuffer, PushError};
+#[cfg(feature = "alloc")]
+pub use ring::SeqRing;
+pub use stats::SeqStats;
+pub use tracker::{Arrival, SeqTracker};
+pub use window::{
+    FixedSeqWindow, FixedSeqWindow64, FixedSeqWindow128, FixedSeqWindow256, FixedSeqWindow1024,
+    SeqWindow, WindowResult,
+};
+
 use core::cmp::Ordering;
 use core::fmt::Debug;
-use core::ops::{Add, Sub};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, BitAnd, BitOr, BitXor, Rem, Sub, SubAssign};
 
 mod seal {
     pub trait Sealed {}
@@ -8,28 +57,44 @@ mod seal {
     impl Sealed for u16 {}
     impl Sealed for u32 {}
     impl Sealed for u64 {}
+    impl Sealed for super::GreaterWins {}
+    impl Sealed for super::LessWins {}
 }
 
-pub trait UInt: seal::Sealed + Copy + Clone + Debug + PartialEq + Eq + Ord {
+pub trait UInt: seal::Sealed + Copy + Clone + Debug + PartialEq + Eq + Ord + Hash {
     const BITS: u32;
     const MAX: Self;
     const ZERO: Self;
     const ONE: Self;
 
+    /// Signed integer of the same width.
+    type Signed: Copy + Clone + Debug + Default + PartialEq + Eq + Ord + Hash;
+
     fn wrapping_add(self, rhs: Self) -> Self;
     fn wrapping_sub(self, rhs: Self) -> Self;
     fn shl(self, by: u32) -> Self; // left shift (by < BITS)
+    fn shr(self, by: u32) -> Self; // right shift (by < BITS)
     fn bit_and(self, rhs: Self) -> Self;
+    fn bit_or(self, rhs: Self) -> Self;
+    fn bit_xor(self, rhs: Self) -> Self;
+    fn rem(self, rhs: Self) -> Self;
+    fn to_u64(self) -> u64;
+    fn to_u128(self) -> u128;
+    fn truncate_from_u64(v: u64) -> Self; // keeps the low Self::BITS bits
+    fn signed_from_i64(v: i64) -> Self::Signed; // keeps the low Self::BITS bits
+    fn to_signed(self) -> Self::Signed; // same bits, reinterpreted
 }
 
 macro_rules! impl_uint {
-    ($t:ty) => {
+    ($t:ty, $s:ty) => {
         impl UInt for $t {
             const BITS: u32 = <$t>::BITS;
             const MAX: $t = <$t>::MAX;
             const ZERO: $t = 0 as $t;
             const ONE: $t = 1 as $t;
 
+            type Signed = $s;
+
             #[inline]
             fn wrapping_add(self, rhs: Self) -> Self {
                 <$t>::wrapping_add(self, rhs)
@@ -43,37 +108,179 @@ macro_rules! impl_uint {
                 self.wrapping_shl(by)
             }
             #[inline]
+            fn shr(self, by: u32) -> Self {
+                self.wrapping_shr(by)
+            }
+            #[inline]
             fn bit_and(self, rhs: Self) -> Self {
                 self & rhs
             }
+            #[inline]
+            fn bit_or(self, rhs: Self) -> Self {
+                self | rhs
+            }
+            #[inline]
+            fn bit_xor(self, rhs: Self) -> Self {
+                self ^ rhs
+            }
+            #[inline]
+            fn rem(self, rhs: Self) -> Self {
+                self % rhs
+            }
+            #[inline]
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+            #[inline]
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+            #[inline]
+            fn truncate_from_u64(v: u64) -> Self {
+                v as $t
+            }
+            #[inline]
+            fn signed_from_i64(v: i64) -> Self::Signed {
+                v as $s
+            }
+            #[inline]
+            fn to_signed(self) -> Self::Signed {
+                self as $s
+            }
         }
     };
 }
 
-impl_uint!(u8);
-impl_uint!(u16);
-impl_uint!(u32);
-impl_uint!(u64);
+impl_uint!(u8, i8);
+impl_uint!(u16, i16);
+impl_uint!(u32, i32);
+impl_uint!(u64, i64);
+
+/// How two values exactly half the space apart compare, which RFC 1982 leaves
+/// undefined. Whatever the policy, the result is the same from both sides.
+pub trait TieBreak: seal::Sealed + Copy + Clone + Debug + 'static {
+    /// `a.cmp(&b)` when `b - a == half_range`.
+    const TIE: Ordering;
+}
+
+/// The default [`TieBreak`]: at exactly half-range apart, both values compare
+/// greater than each other.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GreaterWins;
+
+impl TieBreak for GreaterWins {
+    const TIE: Ordering = Ordering::Greater;
+}
+
+/// [`TieBreak`] where, at exactly half-range apart, both values compare less
+/// than each other.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LessWins;
+
+impl TieBreak for LessWins {
+    const TIE: Ordering = Ordering::Less;
+}
+
+/// Which quarter of the sequence space a value falls in relative to an
+/// origin, from [`SequenceInt::quadrant`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Quadrant {
+    /// Less than a quarter of the space ahead (including the origin itself).
+    NearAhead,
+    /// A quarter up to half the space ahead.
+    FarAhead,
+    /// Half up to a quarter of the space behind.
+    FarBehind,
+    /// Less than a quarter of the space behind.
+    NearBehind,
+}
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone)]
 #[repr(transparent)]
-pub struct SequenceInt<T, const BITS: u8>(pub T)
+pub struct SequenceInt<T, const BITS: u8, P = GreaterWins>(pub T, PhantomData<P>)
 where
-    T: UInt;
+    T: UInt,
+    P: TieBreak;
+
+// Spells out e.g. "BITS (16) exceeds storage width u8 (8)"; const panics
+// can't format integers, so the message is assembled by hand.
+const fn width_error(bits: u8, storage: u32) -> ! {
+    const fn push(buf: &mut [u8; 64], mut len: usize, s: &[u8]) -> usize {
+        let mut i = 0;
+        while i < s.len() {
+            buf[len] = s[i];
+            len += 1;
+            i += 1;
+        }
+        len
+    }
+    const fn push_num(buf: &mut [u8; 64], len: usize, n: u32) -> usize {
+        let mut digits = [0u8; 10];
+        let (mut n, mut i) = (n, digits.len());
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        push(buf, len, digits.split_at(i).1)
+    }
+
+    let mut buf = [0u8; 64];
+    let mut len = push(&mut buf, 0, b"BITS (");
+    len = push_num(&mut buf, len, bits as u32);
+    if bits == 0 {
+        len = push(&mut buf, len, b") must be at least 1");
+    } else {
+        len = push(&mut buf, len, b") exceeds storage width u");
+        len = push_num(&mut buf, len, storage);
+        len = push(&mut buf, len, b" (");
+        len = push_num(&mut buf, len, storage);
+        len = push(&mut buf, len, b")");
+    }
+    match core::str::from_utf8(buf.split_at(len).0) {
+        Ok(msg) => panic!("{}", msg),
+        Err(_) => panic!("invalid sequence width"),
+    }
+}
 
-impl<T, const BITS: u8> SequenceInt<T, BITS>
+impl<T, const BITS: u8, P> SequenceInt<T, BITS, P>
 where
     T: UInt,
+    P: TieBreak,
 {
+    // Evaluated wherever the width is used, so a bad `BITS` fails the build
+    // once the type is instantiated rather than misbehaving at runtime.
+    const VALID_WIDTH: () = {
+        if BITS == 0 || BITS as u32 > T::BITS {
+            width_error(BITS, T::BITS)
+        }
+    };
+
     #[inline]
     const fn is_full_width() -> bool {
+        let () = Self::VALID_WIDTH;
         (BITS as u32) == T::BITS
     }
 
+    /// Number of distinct values in the sequence space, `2^BITS`, as a `u128`
+    /// so that it doesn't overflow for 64-bit widths.
+    pub const MODULUS_U128: u128 = 1 << BITS;
+
+    /// [`MODULUS_U128`](Self::MODULUS_U128), e.g. for reporting how much of
+    /// the space a span covers.
+    #[must_use]
+    #[inline]
+    pub const fn modulus() -> u128 {
+        Self::MODULUS_U128
+    }
+
+    /// The largest value in the sequence space, `2^BITS - 1`.
+    #[must_use]
     #[inline]
-    fn mod_mask() -> T {
-        debug_assert!(BITS >= 1, "BITS must be >= 1");
-        debug_assert!((BITS as u32) <= T::BITS, "BITS must be <= storage width");
+    pub fn modulus_mask() -> T {
         if Self::is_full_width() {
             T::MAX
         } else {
@@ -93,147 +300,2481 @@ where
         if Self::is_full_width() {
             v
         } else {
-            v.bit_and(Self::mod_mask())
+            v.bit_and(Self::modulus_mask())
+        }
+    }
+
+    /// Strict alternative to `From<T>`: fails instead of masking when `value`
+    /// doesn't fit in `BITS` bits.
+    #[inline]
+    pub fn new(value: T) -> Result<Self, OutOfRange> {
+        if value > Self::modulus_mask() {
+            Err(OutOfRange {
+                value: value.to_u128(),
+                bits: BITS,
+                storage_bits: T::BITS,
+            })
+        } else {
+            Ok(Self(value, PhantomData))
         }
     }
 
+    /// Wraps `value` as is, without masking, e.g. in const contexts.
+    ///
+    /// `value` should fit in `BITS` bits. An over-range value isn't unsafe:
+    /// equality and hashing see it masked, as [`get`](Self::get) does, but
+    /// other operations may not.
+    #[must_use]
+    #[inline]
+    pub const fn new_unchecked(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Like [`new`](Self::new), but clamps an over-range `value` to
+    /// [`modulus_mask`](Self::modulus_mask) instead of failing.
+    #[must_use]
+    #[inline]
+    pub fn new_saturating(value: T) -> Self {
+        Self(value.min(Self::modulus_mask()), PhantomData)
+    }
+
+    /// Compares the raw value, as a plain integer, against `other_abs`. This
+    /// ignores wrapping entirely, e.g. to sort log entries of mixed widths
+    /// by value; use [`Ord::cmp`] for sequence order.
+    #[must_use]
+    #[inline]
+    pub fn abs_cmp(self, other_abs: u128) -> Ordering {
+        self.to_u128().cmp(&other_abs)
+    }
+
+    /// A sequence number in the same space holding `value`, masked like
+    /// `From<T>`.
+    #[must_use]
+    #[inline]
+    pub fn with_value(self, value: T) -> Self {
+        Self::from(value)
+    }
+
+    /// The value in the sequence space, masked to `BITS` bits even if it was
+    /// stored unmasked by [`new_unchecked`](Self::new_unchecked).
+    #[must_use]
+    #[inline]
+    pub fn get(self) -> T {
+        Self::mask(self.0)
+    }
+
+    /// The inner value exactly as stored, which only differs from
+    /// [`get`](Self::get) after misuse of
+    /// [`new_unchecked`](Self::new_unchecked).
+    #[must_use]
+    #[inline]
+    pub const fn raw_storage(self) -> T {
+        self.0
+    }
+
+    /// The value widened to `u128`, whatever the storage type.
+    #[must_use]
+    #[inline]
+    pub fn to_u128(self) -> u128 {
+        Self::mask(self.0).to_u128()
+    }
+
+    /// The value and the width, `(get(), BITS)`, for erasing the width at a
+    /// boundary; [`DynSeq::from_parts`] takes them back.
+    #[must_use]
+    #[inline]
+    pub fn into_parts(self) -> (T, u8) {
+        (self.get(), BITS)
+    }
+
+    /// Whether this is the first value of the space, zero.
+    #[must_use]
+    #[inline]
+    pub fn is_zero(self) -> bool {
+        self.get() == T::ZERO
+    }
+
+    /// Whether this is the last value before the wrap, `2^BITS - 1`.
+    #[must_use]
+    #[inline]
+    pub fn is_max(self) -> bool {
+        self.get() == Self::modulus_mask()
+    }
+
     pub fn inc(&mut self) {
         self.0 = Self::mask(self.0.wrapping_add(T::ONE));
     }
     pub fn dec(&mut self) {
         self.0 = Self::mask(self.0.wrapping_sub(T::ONE));
     }
-}
 
-impl<T, const BITS: u8> From<T> for SequenceInt<T, BITS>
-where
-    T: UInt,
-{
+    /// Advances `self` by `n` in place, wrapping at the top of the space.
     #[inline]
-    fn from(value: T) -> Self {
-        Self(Self::mask(value))
+    pub fn inc_by(&mut self, n: T) {
+        *self = *self + n;
     }
-}
 
-// Eq and Ord according to RFC 1982 (TCP-style)
-impl<T, const BITS: u8> PartialEq for SequenceInt<T, BITS>
-where
-    T: UInt,
-{
+    /// Moves `self` back by `n` in place, wrapping at zero.
     #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0
+    pub fn dec_by(&mut self, n: T) {
+        *self = *self - n;
     }
-}
-impl<T, const BITS: u8> Eq for SequenceInt<T, BITS> where T: UInt {}
 
-impl<T, const BITS: u8> PartialOrd for SequenceInt<T, BITS>
-where
-    T: UInt,
-{
+    /// Returns the successor of `self`, wrapping at the top of the space. Unlike
+    /// [`inc`](Self::inc), `self` is left untouched:
+    ///
+    /// ```compile_fail
+    /// #![deny(unused_must_use)]
+    /// let s = seqnum::SeqU8::from(255);
+    /// s.next(); // meant `s.inc()`
+    /// ```
+    ///
+    /// ```
+    /// let s = seqnum::SeqU8::from(255);
+    /// assert_eq!(s.next(), seqnum::SeqU8::from(0));
+    /// ```
+    #[must_use = "this returns the successor, without modifying the original; use `inc` to advance in place"]
     #[inline]
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    pub fn next(self) -> Self {
+        self + T::ONE
     }
-}
 
-impl<T, const BITS: u8> Ord for SequenceInt<T, BITS>
-where
-    T: UInt,
-{
+    /// Returns the predecessor of `self`, wrapping at zero. Unlike
+    /// [`dec`](Self::dec), `self` is left untouched.
+    #[must_use = "this returns the predecessor, without modifying the original; use `dec` to step back in place"]
     #[inline]
-    fn cmp(&self, other: &Self) -> Ordering {
-        let a = self.0;
-        let b = other.0;
+    pub fn prev(self) -> Self {
+        self - T::ONE
+    }
+
+    /// [`next`](Self::next), or `None` instead of wrapping to zero.
+    #[must_use]
+    #[inline]
+    pub fn checked_next(self) -> Option<Self> {
+        (!self.is_max()).then(|| self.next())
+    }
+
+    /// [`prev`](Self::prev), or `None` instead of wrapping to the top.
+    #[must_use]
+    #[inline]
+    pub fn checked_prev(self) -> Option<Self> {
+        (!self.is_zero()).then(|| self.prev())
+    }
+
+    /// Moves `self` forward to `target` if `target` is ahead of it, returning
+    /// whether it moved; never moves backward.
+    ///
+    /// "Ahead" is by [`distance`](Self::distance), so a `target` exactly
+    /// half-range away never counts, whatever the [`TieBreak`] policy, and
+    /// repeating the call is always a no-op.
+    #[inline]
+    pub fn advance_to(&mut self, target: Self) -> bool {
+        let ahead = self.distance(target) > 0;
+        if ahead {
+            *self = target;
+        }
+        ahead
+    }
+
+    /// Named form of `self + rhs`, wrapping at the top of the sequence space.
+    #[must_use]
+    #[inline]
+    pub fn wrapping_add(self, rhs: T) -> Self {
+        self + rhs
+    }
+
+    /// `self + rhs`, and whether the sum wrapped past the top of the sequence
+    /// space (possibly more than once, for an `rhs` of `2^BITS` or more).
+    #[must_use]
+    #[inline]
+    pub fn overflowing_add(self, rhs: T) -> (Self, bool) {
+        let wrapped = self.get().to_u128() + rhs.to_u128() >= 1 << BITS;
+        (self + rhs, wrapped)
+    }
+
+    /// Named form of `self - rhs`, wrapping at zero.
+    #[must_use]
+    #[inline]
+    pub fn wrapping_sub(self, rhs: T) -> Self {
+        self - rhs
+    }
+
+    /// Shifts the value left within the `BITS`-bit space: bits shifted past
+    /// the top are dropped rather than left in the storage's spare high bits.
+    /// Shifting by `BITS` or more gives zero.
+    #[must_use]
+    #[inline]
+    pub fn shl_in_space(self, by: u32) -> Self {
+        if by >= BITS as u32 {
+            return Self(T::ZERO, PhantomData);
+        }
+        Self(Self::mask(self.0.shl(by)), PhantomData)
+    }
 
-        // (b - a) mod 2^BITS
-        let mut diff = b.wrapping_sub(a);
-        if !Self::is_full_width() {
-            diff = diff.bit_and(Self::mod_mask());
+    /// Shifts the value right within the `BITS`-bit space. Shifting by `BITS`
+    /// or more gives zero.
+    #[must_use]
+    #[inline]
+    pub fn shr_in_space(self, by: u32) -> Self {
+        if by >= BITS as u32 {
+            return Self(T::ZERO, PhantomData);
         }
+        Self(Self::mask(self.0).shr(by), PhantomData)
+    }
+
+    /// Moves the value unchanged into the wider `B`-bit space over the same
+    /// storage. Fails to build if `B < BITS`.
+    ///
+    /// The numbers keep their values but not their ordering: a pair that
+    /// straddles the wrap in `BITS` bits no longer does in `B` bits.
+    #[must_use]
+    #[inline]
+    pub fn zero_extend<const B: u8>(self) -> SequenceInt<T, B, P> {
+        const { assert!(B >= BITS, "zero_extend target is narrower than the source") };
+        SequenceInt::from(self.0)
+    }
+
+    /// Number of set bits in the value.
+    #[must_use]
+    #[inline]
+    pub fn count_ones(self) -> u32 {
+        Self::mask(self.0).to_u64().count_ones()
+    }
+
+    /// Number of leading zeros counted from bit `BITS - 1`, not from the top
+    /// of the storage type.
+    #[must_use]
+    #[inline]
+    pub fn leading_zeros_in_bits(self) -> u32 {
+        Self::mask(self.0).to_u64().leading_zeros() - (64 - BITS as u32)
+    }
+
+    /// Advances a timestamp ticking at `clock_hz` (e.g. 90 kHz RTP video) by
+    /// `dur`, rounded to the nearest tick.
+    ///
+    /// Computed in integer nanoseconds rather than floating point, so it is
+    /// exact; tick counts beyond `u64::MAX` saturate before wrapping.
+    #[must_use]
+    pub fn advance_by_duration(self, dur: core::time::Duration, clock_hz: u32) -> Self {
+        const NANOS_PER_SEC: u128 = 1_000_000_000;
+        let ticks = (dur.as_nanos() * clock_hz as u128 + NANOS_PER_SEC / 2) / NANOS_PER_SEC;
+        self + T::truncate_from_u64(ticks.min(u64::MAX as u128) as u64)
+    }
+
+    /// Adds a `usize` offset, e.g. a ring-buffer index, without casting at the
+    /// call site.
+    ///
+    /// The offset is reduced modulo `2^BITS`, so offsets too large for `T` wrap
+    /// exactly as if they had been added one step at a time.
+    #[must_use]
+    #[inline]
+    pub fn add_usize(self, offset: usize) -> Self {
+        self + T::truncate_from_u64(offset as u64)
+    }
+
+    // (other - self) mod 2^BITS
+    #[inline]
+    fn diff(self, other: Self) -> T {
+        Self::mask(other.0.wrapping_sub(self.0))
+    }
 
+    // Ordering of `self` against `other`, given `diff(self, other)`
+    #[inline]
+    fn ordering_of(diff: T) -> Ordering {
         if diff == T::ZERO {
             Ordering::Equal
         } else if diff < Self::half_range() {
             Ordering::Less
+        } else if diff == Self::half_range() {
+            P::TIE
         } else {
             Ordering::Greater
         }
     }
-}
 
-impl<T, const BITS: u8> Add<T> for SequenceInt<T, BITS>
-where
-    T: UInt,
-{
-    type Output = Self;
+    // Signed distance given `diff(self, other)`, in `[-half_range, half_range)`
     #[inline]
-    fn add(self, rhs: T) -> Self::Output {
-        let s = self.0.wrapping_add(rhs);
-        Self(Self::mask(s))
+    fn distance_of(diff: T) -> i64 {
+        if diff < Self::half_range() {
+            diff.to_u64() as i64
+        } else {
+            // diff - 2^BITS, without materializing 2^BITS
+            -(Self::modulus_mask().wrapping_sub(diff).to_u64() as i64) - 1
+        }
     }
-}
 
-impl<T, const BITS: u8> Sub<T> for SequenceInt<T, BITS>
-where
-    T: UInt,
-{
-    type Output = Self;
+    /// Signed distance from `self` to `other`: positive when `other` is ahead
+    /// of `self`, negative when it is behind.
+    ///
+    /// At exactly half-range apart the distance is `-half_range` whatever the
+    /// [`TieBreak`] policy, so the sign agrees with [`Ord::cmp`] under the
+    /// default [`GreaterWins`] (and everywhere else under any policy).
+    #[must_use]
     #[inline]
-    fn sub(self, rhs: T) -> Self::Output {
-        let d = self.0.wrapping_sub(rhs);
-        Self(Self::mask(d))
+    pub fn distance(self, other: Self) -> i64 {
+        Self::distance_of(self.diff(other))
     }
-}
 
-// A few common widths
-pub type SeqU8 = SequenceInt<u8, 8>;
-pub type SeqU16 = SequenceInt<u16, 16>;
-pub type SeqU24 = SequenceInt<u32, 24>;
-pub type SeqU32 = SequenceInt<u32, 32>;
-pub type SeqU64 = SequenceInt<u64, 64>;
+    /// Signed distance from `origin` to `self`, in the signed type matching
+    /// the storage: negative when `self` is behind `origin`.
+    ///
+    /// Always fits, since the distance lies in `[-2^(BITS-1), 2^(BITS-1))`.
+    #[must_use]
+    #[inline]
+    pub fn signed_offset_from(self, origin: Self) -> T::Signed {
+        T::signed_from_i64(origin.distance(self))
+    }
 
-#[test]
-fn test_serial_ints() {
-    assert!(SeqU16::from(1000u16) > SeqU16::from(999u16));
-    assert!(SeqU16::from(65530u16) < SeqU16::from(10u16));
-    assert_eq!(SeqU16::from(7u16), SeqU16::from(7u16));
+    /// Returns both `self.cmp(&other)` and `self.distance(other)`, computing the
+    /// modular difference only once.
+    #[must_use]
+    #[inline]
+    pub fn probe(self, other: Self) -> (Ordering, i64) {
+        let diff = self.diff(other);
+        (Self::ordering_of(diff), Self::distance_of(diff))
+    }
 
-    assert!(SeqU24::from(16_777_206u32) < SeqU24::from(16_777_208u32));
-    assert!(SeqU24::from(16_777_206u32) < SeqU24::from(10u32));
-    assert_eq!(SeqU24::from(16_777_226u32), SeqU24::from(10u32));
+    /// [`distance`](Self::distance) to `other`, and whether it can be trusted:
+    /// `true` when it is at most a quarter of the space (`half_range / 2`)
+    /// either way, `false` when it is close enough to the half-range boundary
+    /// that it may be a wrap rather than a genuine reorder.
+    #[must_use]
+    #[inline]
+    pub fn signed_distance_conf(self, other: Self) -> (i64, bool) {
+        let distance = self.distance(other);
+        let confident = distance.unsigned_abs() <= Self::half_range().to_u64() >> 1;
+        (distance, confident)
+    }
 
-    assert_eq!(SeqU32::from(1000u32) + 7u32, SeqU32::from(1007u32));
-    assert_eq!(SeqU32::from(4_294_967_290u32) + 10u32, SeqU32::from(4u32));
+    /// Number of steps forward from `self` to reach `other`, i.e.
+    /// `(other - self) mod 2^BITS`.
+    #[must_use]
+    #[inline]
+    pub fn forward_distance(self, other: Self) -> T {
+        self.diff(other)
+    }
 
-    type S14 = SequenceInt<u32, 14>;
-    let max = (1u32 << 14) - 1;
-    assert!(S14::from(max - 2) < S14::from(5));
-    assert_eq!(S14::from(max) + 2u32, S14::from(1));
+    /// Number of steps forward from `a` to `b`, the unsigned complement to
+    /// [`distance`](Self::distance). Same as `a.forward_distance(b)`.
+    #[must_use]
+    #[inline]
+    pub fn steps_between(a: Self, b: Self) -> T {
+        a.forward_distance(b)
+    }
 
-    type S = SequenceInt<u64, 64>;
-    let a = S::from(u64::MAX - 2);
-    let b = S::from(3u64);
-    assert!(a < b);
-    assert_eq!(S::from(u64::MAX) + 2u64, S::from(1u64));
+    /// Same as [`steps_between`](Self::steps_between), which already stays
+    /// within [`modulus_mask`](Self::modulus_mask) since the difference is
+    /// masked, even for values from [`new_unchecked`](Self::new_unchecked)
+    /// that don't fit in `BITS` bits.
+    #[must_use]
+    #[inline]
+    pub fn saturating_steps_between(a: Self, b: Self) -> T {
+        Self::steps_between(a, b)
+    }
 
-    assert!(SeqU16::from(1000u16) > SeqU16::from(999u16));
-    assert!(SeqU16::from(65530u16) < SeqU16::from(10u16));
-    assert!(SeqU16::from(7u16) == SeqU16::from(7u16));
+    /// Whether `self` and `other` are exactly half the sequence space apart,
+    /// the case RFC 1982 leaves undefined. Ordering such a pair falls back on
+    /// the [`TieBreak`] policy, so validators that must reject it can check
+    /// here first.
+    #[must_use]
+    #[inline]
+    pub fn is_ambiguous_with(self, other: Self) -> bool {
+        self.forward_distance(other) == Self::half_range()
+    }
 
-    assert!(SeqU24::from(16777206u32) < SeqU24::from(16777208u32));
-    assert!(SeqU24::from(16777206u32) < SeqU24::from(10u32));
-    assert!(SeqU24::from(16777226u32) == SeqU24::from(10u32));
+    /// Whether `self`, taken as a cumulative acknowledgement, covers `other`,
+    /// i.e. `other <= self`.
+    ///
+    /// Only meaningful while unacknowledged data spans less than half the
+    /// sequence space: anything more than half-range behind `self` reads as
+    /// ahead of it, and so as not covered.
+    #[must_use]
+    #[inline]
+    pub fn covers(self, other: Self) -> bool {
+        other <= self
+    }
 
-    assert_eq!(SeqU32::from(1000u32) + 7, SeqU32::from(1007u32));
-    assert_eq!(SeqU32::from(4294967290u32) + 10, SeqU32::from(4u32));
+    /// How far a received `self` is ahead of the receiver's `next_expected`,
+    /// i.e. the size of the gap in front of it; `0` when it's exactly the one
+    /// expected.
+    ///
+    /// This is a forward distance, so a late `self` (behind `next_expected`)
+    /// shows up as a huge gap; check with `self < next_expected` first.
+    #[must_use]
+    #[inline]
+    pub fn gap_from_expected(self, next_expected: Self) -> T {
+        next_expected.forward_distance(self)
+    }
 
-    let mut x = SeqU32::from(0xffff_fffe);
-    x.inc();
-    assert_eq!(x, SeqU32::from(0xffff_ffff_u32));
-    x.inc();
-    assert_eq!(x, SeqU32::from(0));
+    /// Translates `self` from a stream numbered from `old_origin` to one
+    /// numbered from `new_origin`, keeping its forward distance from the
+    /// origin: `new_origin + old_origin.forward_distance(self)`. For relays
+    /// that renumber.
+    #[must_use]
+    #[inline]
+    pub fn rebase(self, old_origin: Self, new_origin: Self) -> Self {
+        new_origin + old_origin.forward_distance(self)
+    }
 
-    assert!(SeqU16::from(1000) < SeqU16::from(33000));
-    assert!(SeqU16::from(1000) > SeqU16::from(34000));
+    /// Fraction of a transfer of `total` steps from `start` that `self` has
+    /// reached, clamped to `[0, 1]`. A zero `total` counts as complete.
+    ///
+    /// As with [`gap_from_expected`](Self::gap_from_expected), a `self` behind
+    /// `start` is a long way forward from it and reads as complete.
+    #[must_use]
+    pub fn progress(self, start: Self, total: T) -> f64 {
+        if total == T::ZERO {
+            return 1.0;
+        }
+        let done = start.forward_distance(self).to_u64() as f64 / total.to_u64() as f64;
+        done.min(1.0)
+    }
+
+    /// Midpoint of the forward arc from `self` to `other`, rounded toward
+    /// `self`.
+    #[must_use]
+    #[inline]
+    pub fn midpoint_forward(self, other: Self) -> Self {
+        self + self.forward_distance(other).shr(1)
+    }
+
+    /// Midpoint of the backward arc from `self` to `other`, i.e. the one
+    /// [`midpoint_forward`](Self::midpoint_forward) doesn't take, rounded
+    /// toward `self`.
+    #[must_use]
+    #[inline]
+    pub fn midpoint_backward(self, other: Self) -> Self {
+        self - other.forward_distance(self).shr(1)
+    }
+
+    /// The point `num / den` of the way along the forward arc from `self` to
+    /// `other`, rounded down (toward `self`). A fraction above one carries on
+    /// past `other`, wrapping as needed.
+    ///
+    /// # Panics
+    ///
+    /// If `den` is zero.
+    #[must_use]
+    pub fn lerp(self, other: Self, num: T, den: T) -> Self {
+        assert!(den != T::ZERO, "lerp denominator must be non-zero");
+        let span = self.forward_distance(other).to_u128();
+        let step = span * num.to_u128() / den.to_u128();
+        self + T::truncate_from_u64(step as u64)
+    }
+
+    /// Binary search over the forward arc `[self, other)` for the first value
+    /// where `pred` fails, given that it holds for a prefix of the arc and
+    /// fails for the rest. Returns `other` if it holds throughout.
+    ///
+    /// # Panics
+    ///
+    /// If `other` is more than half-range ahead of `self`, where the arc is
+    /// likely not the one meant.
+    pub fn bisect(self, other: Self, pred: impl Fn(Self) -> bool) -> Self {
+        assert!(
+            self.forward_distance(other) <= Self::half_range(),
+            "bisect endpoints are more than half the sequence space apart"
+        );
+        let (mut lo, mut hi) = (self, other);
+        while lo != hi {
+            let mid = lo.midpoint_forward(hi);
+            if pred(mid) {
+                lo = mid.next();
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// Coarse position of `self` relative to `origin`, in quarters of the
+    /// sequence space. Exactly half-range away counts as behind, as with
+    /// [`distance`](Self::distance).
+    #[must_use]
+    pub fn quadrant(self, origin: Self) -> Quadrant {
+        let ahead = origin.forward_distance(self);
+        let half = Self::half_range();
+        // rounded up, so a 1-bit space still has `origin` itself near ahead
+        let quarter = half.wrapping_add(T::ONE).shr(1);
+        if ahead < quarter {
+            Quadrant::NearAhead
+        } else if ahead < half {
+            Quadrant::FarAhead
+        } else if ahead.wrapping_sub(half) < quarter {
+            Quadrant::FarBehind
+        } else {
+            Quadrant::NearBehind
+        }
+    }
+
+    /// Clamps `self` to the arc `[center - radius, center + radius]`, snapping
+    /// a value outside it to whichever edge is on its side of `center`.
+    ///
+    /// A `radius` of half-range or more covers the whole space.
+    #[must_use]
+    pub fn circular_clamp(self, center: Self, radius: T) -> Self {
+        if radius >= Self::half_range() {
+            return self;
+        }
+        let offset = center.distance(self);
+        let limit = radius.to_u64() as i64;
+        if offset > limit {
+            center + radius
+        } else if offset < -limit {
+            center - radius
+        } else {
+            self
+        }
+    }
+
+    /// Whether `self` lies on the forward arc `[start, end)`: from `start` up to,
+    /// but excluding, `end`.
+    ///
+    /// `start == end` is the empty span, so this is then always `false`. Same
+    /// as [`SeqRange::contains`].
+    #[must_use]
+    #[inline]
+    pub fn in_half_open(self, start: Self, end: Self) -> bool {
+        start.forward_distance(self) < start.forward_distance(end)
+    }
+
+    /// Whether `self` is one of the `size` values from `start` on, i.e.
+    /// within the acceptance window `[start, start + size)`.
+    #[must_use]
+    #[inline]
+    pub fn in_window(self, start: Self, size: T) -> bool {
+        start.forward_distance(self) < size
+    }
+
+    /// The items of `iter` that are [`in_window`](Self::in_window), in their
+    /// original order.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn collect_in_window<I: IntoIterator<Item = Self>>(
+        iter: I,
+        start: Self,
+        size: T,
+    ) -> alloc::vec::Vec<Self> {
+        iter.into_iter()
+            .filter(|s| s.in_window(start, size))
+            .collect()
+    }
+
+    /// Whether `self` lies strictly inside the forward arc from `lo` to `hi`,
+    /// excluding both.
+    ///
+    /// The interior is empty, so this is always `false`, when `hi` is
+    /// `lo + 1` and, as with [`in_half_open`](Self::in_half_open), when
+    /// `lo == hi`.
+    #[must_use]
+    #[inline]
+    pub fn between_exclusive(self, lo: Self, hi: Self) -> bool {
+        let offset = lo.forward_distance(self);
+        offset != T::ZERO && offset < lo.forward_distance(hi)
+    }
+
+    /// Key for sorting (`sort_by_key`, `BinaryHeap`, ...) by the forward
+    /// distance from `origin`.
+    ///
+    /// Unlike [`Ord`], this is a proper total order, wrapping once at `origin`:
+    /// every value sorts after `origin` and before `origin - 1`.
+    #[must_use]
+    #[inline]
+    pub fn cmp_key(self, origin: Self) -> T {
+        origin.forward_distance(self)
+    }
+
+    /// Compares `self` and `other` by their forward distance from `origin`.
+    ///
+    /// Unlike [`Ord::cmp`] this is transitive over any set of values, as long
+    /// as `origin` is at or before all of them.
+    #[must_use]
+    #[inline]
+    pub fn cmp_from(self, other: Self, origin: Self) -> Ordering {
+        self.cmp_key(origin).cmp(&other.cmp_key(origin))
+    }
+
+    /// Sorts `slice` by forward distance from `origin`, i.e. by
+    /// [`cmp_key`](Self::cmp_key).
+    #[inline]
+    pub fn sort_relative(slice: &mut [Self], origin: Self) {
+        slice.sort_unstable_by_key(|s| s.cmp_key(origin));
+    }
+
+    /// The permutation [`sort_relative`](Self::sort_relative) would apply,
+    /// without moving anything: indices into `slice` in order of forward
+    /// distance from `origin`, equal values keeping their relative order.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn circular_sort_indices(slice: &[Self], origin: Self) -> alloc::vec::Vec<usize> {
+        let mut indices = (0..slice.len()).collect::<alloc::vec::Vec<_>>();
+        indices.sort_by_key(|&i| slice[i].cmp_key(origin));
+        indices
+    }
+
+    /// Like [`Ord::cmp`], but an error instead of the [`TieBreak`] policy's
+    /// answer when the order is undefined; see
+    /// [`is_ambiguous_with`](Self::is_ambiguous_with).
+    #[inline]
+    pub fn cmp_result(self, other: Self) -> Result<Ordering, Ambiguous> {
+        if self.is_ambiguous_with(other) {
+            return Err(Ambiguous);
+        }
+        Ok(self.cmp(&other))
+    }
+
+    /// Like [`Ord::cmp`], but only for values at most `window` steps apart
+    /// (the shorter way round); anything further is an error rather than
+    /// being ordered by the half-range rule.
+    #[inline]
+    pub fn cmp_within(self, other: Self, window: T) -> Result<Ordering, OutOfWindow> {
+        let distance = self.abs_diff(other);
+        if distance > window {
+            return Err(OutOfWindow {
+                distance: distance.to_u64(),
+                window: window.to_u64(),
+            });
+        }
+        Ok(self.cmp(&other))
+    }
+
+    /// Whichever of `self` and `other` is further forward from `origin`
+    /// (`self` if they are equal).
+    #[must_use]
+    #[inline]
+    pub fn newer(self, other: Self, origin: Self) -> Self {
+        match self.cmp_from(other, origin) {
+            Ordering::Less => other,
+            _ => self,
+        }
+    }
+
+    /// Whichever of `self` and `other` is closer forward from `origin`
+    /// (`self` if they are equal).
+    #[must_use]
+    #[inline]
+    pub fn older(self, other: Self, origin: Self) -> Self {
+        match self.cmp_from(other, origin) {
+            Ordering::Greater => other,
+            _ => self,
+        }
+    }
+
+    /// Number of steps between `self` and `other` the shorter way round the
+    /// circle, at most `half_range`.
+    #[must_use]
+    #[inline]
+    pub fn abs_diff(self, other: Self) -> T {
+        self.diff(other).min(other.diff(self))
+    }
+
+    /// Whether `self` and `other` are within `tol` steps of each other the
+    /// shorter way round, i.e. `self.abs_diff(other) <= tol`.
+    #[must_use]
+    #[inline]
+    pub fn approx_eq(self, other: Self, tol: T) -> bool {
+        self.abs_diff(other) <= tol
+    }
+
+    /// The candidate with the smallest [`abs_diff`](Self::abs_diff) to `self`
+    /// (the first of any ties), or `None` if there are none.
+    #[must_use]
+    pub fn nearest(self, candidates: &[Self]) -> Option<Self> {
+        candidates.iter().copied().min_by_key(|&c| self.abs_diff(c))
+    }
+
+    /// Alias for [`distance`](Self::distance), for callers that want a
+    /// metric bounded by half the space, e.g. for skew estimation.
+    ///
+    /// `distance` already lies in `[-half_range, half_range)`, however far
+    /// apart the raw values are, so there is nothing left to clamp.
+    #[must_use]
+    #[inline]
+    pub fn saturating_distance(self, other: Self) -> i64 {
+        self.distance(other)
+    }
+
+    /// `self - rhs`, or `None` when the two are exactly half-range apart and
+    /// the direction of the delta is ambiguous.
+    ///
+    /// The `-` operator instead picks `-half_range` in that case.
+    #[must_use]
+    #[inline]
+    pub fn checked_sub_self(self, rhs: Self) -> Option<SeqDelta<T, BITS>> {
+        let diff = rhs.diff(self);
+        if diff == Self::half_range() {
+            None
+        } else {
+            Some(SeqDelta::new(Self::distance_of(diff)))
+        }
+    }
+
+    /// [`distance`](Self::distance) for values behind references.
+    #[must_use]
+    #[inline]
+    pub fn distance_ref(&self, other: &Self) -> i64 {
+        self.distance(*other)
+    }
+}
+
+// `SequenceInt<24>(10)`; the inner value honours the formatter's flags, so
+// `{:#x?}` gives `SequenceInt<24>(0xa)`. Widths of 32 bits and up are always
+// in hex, `SequenceInt<64>(0xdeadbeef)`, since long decimals are hard to
+// compare by eye in packet traces.
+impl<T, const BITS: u8, P> Debug for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "SequenceInt<{BITS}>(")?;
+        if BITS >= 32 {
+            write!(f, "{:#x}", self.0.to_u64())?;
+        } else {
+            self.0.fmt(f)?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<T, const BITS: u8, P> From<T> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(Self::mask(value), PhantomData)
+    }
+}
+
+// Eq and Ord according to RFC 1982 (TCP-style)
+impl<T, const BITS: u8, P> PartialEq for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+impl<T, const BITS: u8, P> Eq for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+}
+
+// Hashes exactly like the masked inner `T`, so values that compare equal hash
+// the same however they were built. There's deliberately no `Borrow<T>`:
+// it requires `T`'s order to agree with ours, and RFC 1982 order doesn't, so
+// look a raw value up by wrapping it, `map.get(&SeqU32::from(raw))`.
+impl<T, const BITS: u8, P> Hash for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state);
+    }
+}
+
+impl<T, const BITS: u8, P> AsRef<T> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, const BITS: u8, P> PartialOrd for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// RFC 1982 leaves the comparison undefined when the two values are exactly
+// `half_range` apart. The `TieBreak` policy resolves that case to the same
+// answer from both sides, so with the default `GreaterWins` both `a > b` and
+// `b > a` hold for such a pair; every other non-equal pair orders
+// antisymmetrically (exactly one of `a < b` / `b < a`).
+impl<T, const BITS: u8, P> Ord for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        if Self::is_full_width() {
+            // No masking needed, and the sign of the wrapped difference is the
+            // answer: positive when `other` is ahead, i.e. `self < other`.
+            let diff = other.0.wrapping_sub(self.0);
+            if diff == Self::half_range() {
+                return P::TIE;
+            }
+            return T::Signed::default().cmp(&diff.to_signed());
+        }
+        Self::ordering_of(self.diff(*other))
+    }
+}
+
+// Wrapping in the storage type first is harmless: 2^BITS divides 2^T::BITS,
+// so reducing mod 2^T::BITS and then masking is the same as reducing mod
+// 2^BITS directly, whatever the size of `rhs`. The same goes for `Sub<T>`.
+impl<T, const BITS: u8, P> Add<T> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: T) -> Self::Output {
+        let s = self.0.wrapping_add(rhs);
+        Self(Self::mask(s), PhantomData)
+    }
+}
+
+impl<T, const BITS: u8, P> Sub<T> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: T) -> Self::Output {
+        let d = self.0.wrapping_sub(rhs);
+        Self(Self::mask(d), PhantomData)
+    }
+}
+
+// `a + b` treats `b` as an offset, i.e. it is `a + b.0`
+impl<T, const BITS: u8, P> Add for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self + rhs.0
+    }
+}
+
+impl<T, const BITS: u8, P> AddAssign for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+// Offset semantics like `+=`: `a -= b` is `a = a - b.0`, moving `a` back by
+// `b`. This is *not* `a = a - b`, which is the `SeqDelta` from `Sub<Self>`.
+impl<T, const BITS: u8, P> SubAssign for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs.0;
+    }
+}
+
+// `a - b` is the signed delta from `b` to `a` (`b.distance(a)`), so that
+// `b + (a - b) == a`; use `forward_distance` for the unsigned `(a - b) mod 2^BITS`
+impl<T, const BITS: u8, P> Sub for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = SeqDelta<T, BITS>;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        SeqDelta::new(rhs.distance(self))
+    }
+}
+
+impl<T, const BITS: u8, P> Sub<&SequenceInt<T, BITS, P>> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = SeqDelta<T, BITS>;
+    #[inline]
+    fn sub(self, rhs: &Self) -> Self::Output {
+        self - *rhs
+    }
+}
+
+impl<T, const BITS: u8, P> Sub<SequenceInt<T, BITS, P>> for &SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = SeqDelta<T, BITS>;
+    #[inline]
+    fn sub(self, rhs: SequenceInt<T, BITS, P>) -> Self::Output {
+        *self - rhs
+    }
+}
+
+impl<T, const BITS: u8, P> Sub<&SequenceInt<T, BITS, P>> for &SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = SeqDelta<T, BITS>;
+    #[inline]
+    fn sub(self, rhs: &SequenceInt<T, BITS, P>) -> Self::Output {
+        *self - *rhs
+    }
+}
+
+// The remaining reference forms of `Add<T>`, `Sub<T>` and `Add<Self>`, like
+// std's for the primitive integers; each just dereferences and forwards
+macro_rules! forward_ref_binop {
+    ($tr:ident, $method:ident, $rhs:ty) => {
+        impl<T, const BITS: u8, P> $tr<$rhs> for &SequenceInt<T, BITS, P>
+        where
+            T: UInt,
+            P: TieBreak,
+        {
+            type Output = SequenceInt<T, BITS, P>;
+            #[inline]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl<T, const BITS: u8, P> $tr<&$rhs> for SequenceInt<T, BITS, P>
+        where
+            T: UInt,
+            P: TieBreak,
+        {
+            type Output = Self;
+            #[inline]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                self.$method(*rhs)
+            }
+        }
+
+        impl<T, const BITS: u8, P> $tr<&$rhs> for &SequenceInt<T, BITS, P>
+        where
+            T: UInt,
+            P: TieBreak,
+        {
+            type Output = SequenceInt<T, BITS, P>;
+            #[inline]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                (*self).$method(*rhs)
+            }
+        }
+    };
+}
+
+forward_ref_binop!(Add, add, T);
+forward_ref_binop!(Sub, sub, T);
+forward_ref_binop!(Add, add, SequenceInt<T, BITS, P>);
+
+// `seq % n` is the raw value modulo `n`: a bucket index in `0..n` for spreading
+// sequence numbers over `n` partitions, not a sequence number itself. Panics if
+// `n` is zero.
+impl<T, const BITS: u8, P> Rem<T> for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = T;
+    #[inline]
+    fn rem(self, rhs: T) -> Self::Output {
+        self.0.rem(rhs)
+    }
+}
+
+// Bitwise ops against a raw `T`, e.g. for hashing or partitioning on the low
+// bits. The result is masked back to `BITS`, so `seq ^ T::MAX` flips only the
+// bits within the sequence space.
+macro_rules! impl_bit_op {
+    ($tr:ident, $method:ident, $op:ident) => {
+        impl<T, const BITS: u8, P> $tr<T> for SequenceInt<T, BITS, P>
+        where
+            T: UInt,
+            P: TieBreak,
+        {
+            type Output = Self;
+            #[inline]
+            fn $method(self, rhs: T) -> Self::Output {
+                Self(Self::mask(self.0.$op(rhs)), PhantomData)
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, bit_and);
+impl_bit_op!(BitOr, bitor, bit_or);
+impl_bit_op!(BitXor, bitxor, bit_xor);
+
+// Trait methods can't be called in const contexts, so the const comparison is
+// spelled out per storage type
+macro_rules! impl_const_cmp {
+    ($t:ty) => {
+        impl<const BITS: u8, P: TieBreak> SequenceInt<$t, BITS, P> {
+            /// [`Ord::cmp`], usable in const contexts.
+            #[must_use]
+            #[inline]
+            pub const fn const_cmp(self, other: Self) -> Ordering {
+                let () = Self::VALID_WIDTH;
+                let mask = <$t>::MAX >> (<$t>::BITS - BITS as u32);
+                let diff = other.0.wrapping_sub(self.0) & mask;
+                if diff == 0 {
+                    Ordering::Equal
+                } else if diff < 1 << (BITS - 1) {
+                    Ordering::Less
+                } else if diff == 1 << (BITS - 1) {
+                    P::TIE
+                } else {
+                    Ordering::Greater
+                }
+            }
+        }
+    };
+}
+
+impl_const_cmp!(u8);
+impl_const_cmp!(u16);
+impl_const_cmp!(u32);
+impl_const_cmp!(u64);
+
+// A few common widths
+pub type SeqU8 = SequenceInt<u8, 8>;
+pub type SeqU16 = SequenceInt<u16, 16>;
+pub type SeqU24 = SequenceInt<u32, 24>;
+pub type SeqU32 = SequenceInt<u32, 32>;
+pub type SeqU64 = SequenceInt<u64, 64>;
+
+#[test]
+fn test_serial_ints() {
+    assert!(SeqU16::from(1000u16) > SeqU16::from(999u16));
+    assert!(SeqU16::from(65530u16) < SeqU16::from(10u16));
+    assert_eq!(SeqU16::from(7u16), SeqU16::from(7u16));
+
+    assert!(SeqU24::from(16_777_206u32) < SeqU24::from(16_777_208u32));
+    assert!(SeqU24::from(16_777_206u32) < SeqU24::from(10u32));
+    assert_eq!(SeqU24::from(16_777_226u32), SeqU24::from(10u32));
+
+    assert_eq!(SeqU32::from(1000u32) + 7u32, SeqU32::from(1007u32));
+    assert_eq!(SeqU32::from(4_294_967_290u32) + 10u32, SeqU32::from(4u32));
+
+    type S14 = SequenceInt<u32, 14>;
+    let max = (1u32 << 14) - 1;
+    assert!(S14::from(max - 2) < S14::from(5));
+    assert_eq!(S14::from(max) + 2u32, S14::from(1));
+
+    type S = SequenceInt<u64, 64>;
+    let a = S::from(u64::MAX - 2);
+    let b = S::from(3u64);
+    assert!(a < b);
+    assert_eq!(S::from(u64::MAX) + 2u64, S::from(1u64));
+
+    assert!(SeqU16::from(1000u16) > SeqU16::from(999u16));
+    assert!(SeqU16::from(65530u16) < SeqU16::from(10u16));
+    assert!(SeqU16::from(7u16) == SeqU16::from(7u16));
+
+    assert!(SeqU24::from(16777206u32) < SeqU24::from(16777208u32));
+    assert!(SeqU24::from(16777206u32) < SeqU24::from(10u32));
+    assert!(SeqU24::from(16777226u32) == SeqU24::from(10u32));
+
+    assert_eq!(SeqU32::from(1000u32) + 7, SeqU32::from(1007u32));
+    assert_eq!(SeqU32::from(4294967290u32) + 10, SeqU32::from(4u32));
+
+    let mut x = SeqU32::from(0xffff_fffe);
+    x.inc();
+    assert_eq!(x, SeqU32::from(0xffff_ffff_u32));
+    x.inc();
+    assert_eq!(x, SeqU32::from(0));
+
+    assert!(SeqU16::from(1000) < SeqU16::from(33000));
+    assert!(SeqU16::from(1000) > SeqU16::from(34000));
+}
+
+// xorshift64, good enough to scatter test inputs without pulling in a dep
+#[cfg(test)]
+fn next_rand(state: &mut u64) -> u64 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    *state = x;
+    x
+}
+
+#[cfg(test)]
+fn check_antisymmetry<T, const BITS: u8>(pairs: usize)
+where
+    T: UInt + TryFrom<u64>,
+{
+    let mut state = 0x9e37_79b9_7f4a_7c15u64 ^ BITS as u64;
+    let mask = u64::MAX >> (64 - BITS as u32);
+    let half = 1u64 << (BITS - 1);
+    for _ in 0..pairs {
+        let raw_a = next_rand(&mut state) & mask;
+        // bias half of the pairs towards the half-range boundary
+        let raw_b = if next_rand(&mut state) & 1 == 0 {
+            next_rand(&mut state) & mask
+        } else {
+            raw_a
+                .wrapping_add(half)
+                .wrapping_add(next_rand(&mut state) % 3)
+                .wrapping_sub(1)
+                & mask
+        };
+        let (Ok(ta), Ok(tb)) = (T::try_from(raw_a), T::try_from(raw_b)) else {
+            unreachable!()
+        };
+        let a = SequenceInt::<T, BITS>::from(ta);
+        let b = SequenceInt::<T, BITS>::from(tb);
+        let ambiguous = raw_b.wrapping_sub(raw_a) & mask == half;
+        if a == b {
+            assert_eq!(a.cmp(&b), Ordering::Equal);
+        } else if ambiguous {
+            assert!(a > b && b > a, "{a:?} / {b:?}");
+        } else {
+            assert!((a < b) ^ (b < a), "{a:?} / {b:?}");
+            assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        }
+    }
+}
+
+#[test]
+fn test_antisymmetry() {
+    for a in 0..=u8::MAX {
+        for b in 0..=u8::MAX {
+            let (x, y) = (SeqU8::from(a), SeqU8::from(b));
+            if a.wrapping_sub(b) == 128 {
+                assert!(x > y && y > x);
+            } else if a != b {
+                assert!((x < y) ^ (y < x));
+            }
+        }
+    }
+
+    check_antisymmetry::<u8, 8>(10_000);
+    check_antisymmetry::<u16, 16>(10_000);
+    check_antisymmetry::<u32, 24>(10_000);
+    check_antisymmetry::<u32, 32>(10_000);
+    check_antisymmetry::<u64, 64>(10_000);
+    check_antisymmetry::<u32, 14>(10_000);
+}
+
+#[test]
+fn test_distance_probe() {
+    assert_eq!(SeqU16::from(10).distance(SeqU16::from(15)), 5);
+    assert_eq!(SeqU16::from(15).distance(SeqU16::from(10)), -5);
+    assert_eq!(SeqU16::from(65530).distance(SeqU16::from(4)), 10);
+    assert_eq!(SeqU16::from(4).distance(SeqU16::from(65530)), -10);
+    assert_eq!(SeqU16::from(0).distance(SeqU16::from(32768)), -32768);
+    assert_eq!(SeqU16::from(0).distance(SeqU16::from(32767)), 32767);
+    assert_eq!(SeqU64::from(u64::MAX).distance(SeqU64::from(0)), 1);
+    assert_eq!(SeqU64::from(0).distance(SeqU64::from(1 << 63)), i64::MIN);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(16_380).distance(S14::from(3)), 7);
+    assert_eq!(S14::from(3).distance(S14::from(16_380)), -7);
+
+    fn check<T: UInt + TryFrom<u64>, const BITS: u8>() {
+        let mask = u64::MAX >> (64 - BITS as u32);
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for _ in 0..5_000 {
+            let (Ok(a), Ok(b)) = (
+                T::try_from(next_rand(&mut state) & mask),
+                T::try_from(next_rand(&mut state) & mask),
+            ) else {
+                unreachable!()
+            };
+            let (a, b) = (
+                SequenceInt::<T, BITS>::from(a),
+                SequenceInt::<T, BITS>::from(b),
+            );
+            let (ord, dist) = a.probe(b);
+            assert_eq!(ord, a.cmp(&b));
+            assert_eq!(dist, a.distance(b));
+            assert_eq!(ord, 0.cmp(&dist));
+        }
+    }
+    check::<u8, 8>();
+    check::<u16, 16>();
+    check::<u32, 24>();
+    check::<u32, 32>();
+    check::<u64, 64>();
+    check::<u32, 14>();
+}
+
+#[test]
+fn test_signed_distance_conf() {
+    let a = SeqU16::from(65530);
+    assert_eq!(a.signed_distance_conf(SeqU16::from(10)), (16, true));
+    assert_eq!(SeqU16::from(10).signed_distance_conf(a), (-16, true));
+    assert_eq!(a.signed_distance_conf(a), (0, true));
+    // a quarter of the space either way is the edge of confidence
+    assert_eq!(a.signed_distance_conf(a + 16_384), (16_384, true));
+    assert_eq!(a.signed_distance_conf(a + 16_385), (16_385, false));
+    assert_eq!(a.signed_distance_conf(a - 16_384), (-16_384, true));
+    assert_eq!(a.signed_distance_conf(a - 16_385), (-16_385, false));
+    assert_eq!(a.signed_distance_conf(a + 32_767), (32_767, false));
+
+    let (d, conf) = SeqU64::from(0).signed_distance_conf(SeqU64::from(1 << 63));
+    assert!(d.unsigned_abs() == 1 << 63 && !conf);
+    assert!(
+        SeqU64::from(0)
+            .signed_distance_conf(SeqU64::from(1 << 62))
+            .1
+    );
+    assert!(
+        !SequenceInt::<u8, 1>::from(0)
+            .signed_distance_conf(SequenceInt::from(1))
+            .1
+    );
+}
+
+#[test]
+fn test_sub_self_refs() {
+    let a = SeqU16::from(3);
+    let b = SeqU16::from(65533);
+    assert_eq!(a - b, SeqDelta::new(6));
+    assert_eq!(b - a, SeqDelta::new(-6));
+    let (ra, rb) = (&a, &b);
+    assert_eq!(ra - rb, SeqDelta::new(6));
+    assert_eq!(a - rb, SeqDelta::new(6));
+    assert_eq!(ra - b, SeqDelta::new(6));
+
+    let seqs = [
+        SeqU16::from(65534),
+        SeqU16::from(65535),
+        SeqU16::from(0),
+        SeqU16::from(2),
+    ];
+    let gaps = seqs
+        .windows(2)
+        .map(|w| match w {
+            [prev, next] => (next - prev).get(),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(gaps, [1, 1, 2]);
+
+    let origin = SeqU16::from(65534);
+    let mut sorted = seqs;
+    sorted.reverse();
+    sorted.sort_by_key(|s| origin.distance_ref(s));
+    assert_eq!(sorted, seqs);
+
+    let behind = |x: &SeqU16, y: &SeqU16| x.distance_ref(y) < 0;
+    assert!(behind(&seqs[3], &seqs[0]));
+    assert!(!behind(&seqs[0], &seqs[3]));
+}
+
+#[test]
+fn test_ref_operands() {
+    let (a, n) = (SeqU16::from(65534), 5u16);
+    let b = SeqU16::from(5);
+    let (ra, rn, rb) = (&a, &n, &b);
+
+    let sum = SeqU16::from(3);
+    assert_eq!([a + n, ra + n, a + rn, ra + rn], [sum; 4]);
+    assert_eq!([a + b, ra + b, a + rb, ra + rb], [sum; 4]);
+    let diff = SeqU16::from(65529);
+    assert_eq!([a - n, ra - n, a - rn, ra - rn], [diff; 4]);
+    assert_eq!([a - b, ra - b, a - rb, ra - rb], [SeqDelta::new(-7); 4]);
+
+    // usable through generic bounds, as for the primitive integers
+    fn step<'a, S>(s: &'a S, by: &'a u16) -> S
+    where
+        &'a S: core::ops::Add<&'a u16, Output = S>,
+    {
+        s + by
+    }
+    assert_eq!(step(&a, &n), sum);
+}
+
+#[test]
+fn test_next_prev() {
+    let s = SeqU8::from(255);
+    assert_eq!(s.next(), SeqU8::from(0));
+    assert_eq!(s, SeqU8::from(255));
+    assert_eq!(SeqU8::from(0).prev(), s);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(16_383).next(), S14::from(0));
+    assert_eq!(S14::from(0).prev(), S14::from(16_383));
+}
+
+#[test]
+fn test_saturating_distance() {
+    assert_eq!(SeqU16::from(10).saturating_distance(SeqU16::from(15)), 5);
+    assert_eq!(SeqU16::from(15).saturating_distance(SeqU16::from(10)), -5);
+    // the raw gap is far beyond half-range, but the result stays within it
+    assert_eq!(
+        SeqU16::from(65530).saturating_distance(SeqU16::from(10)),
+        16
+    );
+    assert_eq!(
+        SeqU16::from(10).saturating_distance(SeqU16::from(65530)),
+        -16
+    );
+    assert_eq!(
+        SeqU16::from(0).saturating_distance(SeqU16::from(40_000)),
+        -25_536
+    );
+    assert_eq!(
+        SeqU16::from(0).saturating_distance(SeqU16::from(32_768)),
+        -32_768
+    );
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(1).saturating_distance(S14::from(16_000)), -385);
+    assert_eq!(S14::from(16_000).saturating_distance(S14::from(1)), 385);
+    assert_eq!(S14::from(100).saturating_distance(S14::from(8_000)), 7_900);
+
+    let mut state = 0x5a7_u64;
+    for _ in 0..1000 {
+        let a = SeqU16::from(next_rand(&mut state) as u16);
+        let b = SeqU16::from(next_rand(&mut state) as u16);
+        let d = a.saturating_distance(b);
+        assert_eq!(d, a.distance(b));
+        assert!((-32_768..32_768).contains(&d));
+    }
+
+    let (lo, hi) = (SeqU64::from(0), SeqU64::from(u64::MAX));
+    assert_eq!(lo.saturating_distance(hi), -1);
+    assert_eq!(hi.saturating_distance(lo), 1);
+    assert_eq!(lo.saturating_distance(SeqU64::from(1 << 63)), i64::MIN);
+}
+
+#[test]
+fn test_cmp_key() {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    let origin = SeqU16::from(65530);
+    assert_eq!(origin.cmp_key(origin), 0);
+    assert_eq!(SeqU16::from(2).cmp_key(origin), 8);
+    assert_eq!(origin.forward_distance(SeqU16::from(2)), 8);
+    assert_eq!(SeqU16::from(2).forward_distance(origin), 65528);
+
+    let raw = [3u16, 65534, 0, 65531, 10, 65530];
+    let mut heap = raw
+        .iter()
+        .map(|&v| {
+            let s = SeqU16::from(v);
+            (Reverse(s.cmp_key(origin)), v)
+        })
+        .collect::<BinaryHeap<_>>();
+    let mut popped = Vec::new();
+    while let Some((_, v)) = heap.pop() {
+        popped.push(v);
+    }
+    assert_eq!(popped, [65530, 65531, 65534, 0, 3, 10]);
+
+    let mut sorted = raw.map(SeqU16::from);
+    sorted.sort_by_key(|s| s.cmp_key(origin));
+    assert_eq!(sorted.map(|s| s.0), [65530, 65531, 65534, 0, 3, 10]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_circular_sort_indices() {
+    let origin = SeqU16::from(65530);
+    let seqs = [3, 65534, 10, 0, 65530, 3, 65531].map(SeqU16::from);
+    let indices = SeqU16::circular_sort_indices(&seqs, origin);
+    assert_eq!(indices, [4, 6, 1, 3, 0, 5, 2]);
+
+    let mut sorted = seqs;
+    SeqU16::sort_relative(&mut sorted, origin);
+    assert_eq!(indices.iter().map(|&i| seqs[i]).collect::<Vec<_>>(), sorted);
+    assert_eq!(sorted.map(|s| s.0), [65530, 65531, 65534, 0, 3, 3, 10]);
+
+    assert!(SeqU16::circular_sort_indices(&[], origin).is_empty());
+}
+
+#[test]
+fn test_new() {
+    assert_eq!(SeqU24::new(10), Ok(SeqU24::from(10)));
+    assert_eq!(SeqU24::new(0xff_ffff), Ok(SeqU24::from(0xff_ffff)));
+    assert_eq!(
+        SeqU24::new(0x100_0005),
+        Err(OutOfRange {
+            value: 0x100_0005,
+            bits: 24,
+            storage_bits: 32
+        })
+    );
+    assert_eq!(
+        SequenceInt::<u8, 7>::new(200).unwrap_err().to_string(),
+        "200 is out of range for 7 bits"
+    );
+    assert_eq!(SeqU24::new_saturating(10), SeqU24::from(10));
+    assert_eq!(SeqU24::new_saturating(0x100_0005).0, 0xff_ffff);
+    assert_eq!(SeqU24::from(0x100_0005).0, 5);
+
+    assert_eq!(SeqU32::new(u32::MAX), Ok(SeqU32::from(u32::MAX)));
+    assert_eq!(SeqU32::new_saturating(u32::MAX).0, u32::MAX);
+    assert_eq!(SequenceInt::<u32, 14>::modulus_mask(), 16_383);
+}
+
+#[test]
+fn test_debug_shows_width() {
+    assert_eq!(format!("{:?}", SeqU24::from(10)), "SequenceInt<24>(10)");
+    assert_eq!(format!("{:?}", SeqU16::from(10)), "SequenceInt<16>(10)");
+    assert_eq!(format!("{:#x?}", SeqU24::from(10)), "SequenceInt<24>(0xa)");
+    assert_eq!(
+        format!("{:?}", Some(SequenceInt::<u32, 14>::from(3))),
+        "Some(SequenceInt<14>(3))"
+    );
+    assert_eq!(
+        format!("{:?}", SeqU64::from(0xdead_beef)),
+        "SequenceInt<64>(0xdeadbeef)"
+    );
+    assert_eq!(format!("{:?}", SeqU32::from(10)), "SequenceInt<32>(0xa)");
+    assert_eq!(format!("{:x?}", SeqU32::from(10)), "SequenceInt<32>(0xa)");
+}
+
+#[test]
+fn test_hash() {
+    use std::collections::{HashMap, HashSet};
+    use std::hash::{BuildHasher, RandomState};
+
+    let mut map = HashMap::new();
+    map.insert(SeqU32::from(7), "seven");
+    map.insert(SeqU32::from(u32::MAX), "max");
+    assert_eq!(map.get(&SeqU32::from(7)), Some(&"seven"));
+    assert_eq!(map.get(&SeqU32::from(u32::MAX)), Some(&"max"));
+    assert_eq!(map.get(&SeqU32::from(8)), None);
+
+    let hasher = RandomState::new();
+    assert_eq!(hasher.hash_one(SeqU32::from(7)), hasher.hash_one(7u32));
+
+    let set = [5u32, 1 << 24]
+        .map(SeqU24::from)
+        .into_iter()
+        .collect::<HashSet<_>>();
+    assert!(set.contains(&SeqU24::from(5)));
+    assert!(set.contains(&SeqU24::from(0)));
+}
+
+#[test]
+fn test_covers() {
+    let ack = SeqU32::from(u32::MAX - 2);
+    assert!(ack.covers(ack));
+    assert!(ack.covers(SeqU32::from(u32::MAX - 100)));
+    assert!(!ack.covers(SeqU32::from(u32::MAX)));
+    assert!(!ack.covers(SeqU32::from(3)));
+
+    let ack = SeqU32::from(3);
+    assert!(ack.covers(SeqU32::from(u32::MAX - 2)));
+    assert!(ack.covers(SeqU32::from(0)));
+    assert!(!ack.covers(SeqU32::from(4)));
+
+    let ack = SeqU16::from(100);
+    assert!(!ack.covers(SeqU16::from(100 + 32_768)));
+    assert!(ack.covers(SeqU16::from(100 + 32_769)));
+}
+
+#[test]
+fn test_cmp_result() {
+    assert_eq!(
+        SeqU8::from(1).cmp_result(SeqU8::from(2)),
+        Ok(Ordering::Less)
+    );
+    assert_eq!(
+        SeqU8::from(2).cmp_result(SeqU8::from(2)),
+        Ok(Ordering::Equal)
+    );
+    assert_eq!(
+        SeqU8::from(0).cmp_result(SeqU8::from(255)),
+        Ok(Ordering::Greater)
+    );
+    assert_eq!(
+        SeqU8::from(0).cmp_result(SeqU8::from(127)),
+        Ok(Ordering::Less)
+    );
+    assert_eq!(SeqU8::from(0).cmp_result(SeqU8::from(128)), Err(Ambiguous));
+    assert_eq!(SeqU8::from(128).cmp_result(SeqU8::from(0)), Err(Ambiguous));
+    assert_eq!(
+        SequenceInt::<u8, 8, LessWins>::from(3).cmp_result(SequenceInt::from(131)),
+        Err(Ambiguous)
+    );
+}
+
+#[test]
+fn test_rebase() {
+    let (old, new) = (SeqU16::from(1000), SeqU16::from(40_000));
+    assert_eq!(SeqU16::from(1000).rebase(old, new), new);
+    assert_eq!(SeqU16::from(1010).rebase(old, new), SeqU16::from(40_010));
+    // the new base wraps
+    let new = SeqU16::from(65530);
+    assert_eq!(SeqU16::from(1010).rebase(old, new), SeqU16::from(4));
+    // the old base wraps
+    let old = SeqU16::from(65000);
+    assert_eq!(
+        SeqU16::from(100).rebase(old, new),
+        SeqU16::from(100 + 536 - 6)
+    );
+    // and back again
+    let s = SeqU16::from(12_345);
+    assert_eq!(s.rebase(old, new).rebase(new, old), s);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(
+        S14::from(2).rebase(S14::from(16_380), S14::from(100)),
+        S14::from(106)
+    );
+}
+
+#[test]
+fn test_is_ambiguous_with() {
+    for (a, b) in [(0u8, 128u8), (128, 0), (10, 138), (200, 72)] {
+        assert!(SeqU8::from(a).is_ambiguous_with(SeqU8::from(b)));
+    }
+    for (a, b) in [(0u8, 0u8), (0, 127), (0, 129), (10, 137), (255, 0)] {
+        assert!(!SeqU8::from(a).is_ambiguous_with(SeqU8::from(b)));
+    }
+    assert!(SeqU32::from(5).is_ambiguous_with(SeqU32::from(5 + (1 << 31))));
+}
+
+#[cfg(test)]
+fn check_masking<T, const BITS: u8>()
+where
+    T: UInt + TryFrom<u64>,
+{
+    let mask = u64::MAX >> (64 - BITS as u32);
+    let storage_mask = u64::MAX >> (64 - T::BITS);
+    let to_t = |v: u64| match T::try_from(v & storage_mask) {
+        Ok(t) => t,
+        Err(_) => unreachable!(),
+    };
+    let mut state = 0xdead_beef_cafe_f00du64 ^ BITS as u64;
+    for _ in 0..200 {
+        let mut s = SequenceInt::<T, BITS>::from(to_t(next_rand(&mut state)));
+        for _ in 0..50 {
+            let arg = to_t(next_rand(&mut state));
+            match next_rand(&mut state) % 8 {
+                0 => s.inc(),
+                1 => s.dec(),
+                2 => s = s + arg,
+                3 => s = s - arg,
+                4 => s = s.next(),
+                5 => s = s.prev(),
+                6 => s = SequenceInt::new_saturating(arg),
+                _ => s = SequenceInt::from(arg),
+            }
+            let v = s.0.to_u64();
+            assert_eq!(v & mask, v, "{s:?} escaped {BITS} bits");
+            // re-masking is a no-op
+            assert_eq!(SequenceInt::<T, BITS>::from(s.0), s);
+            assert_eq!(SequenceInt::<T, BITS>::new(s.0), Ok(s));
+        }
+    }
+}
+
+#[test]
+fn test_masking_idempotent() {
+    check_masking::<u8, 8>();
+    check_masking::<u16, 16>();
+    check_masking::<u32, 24>();
+    check_masking::<u32, 32>();
+    check_masking::<u64, 64>();
+    check_masking::<u32, 14>();
+    check_masking::<u32, 20>();
+    check_masking::<u8, 3>();
+}
+
+#[test]
+fn test_add_sub_assign_self() {
+    fn check<T: UInt + TryFrom<u64>, const BITS: u8>(pairs: &[(u64, u64)]) {
+        for &(a, b) in pairs {
+            let (Ok(a), Ok(b)) = (T::try_from(a), T::try_from(b)) else {
+                unreachable!()
+            };
+            let (a, b) = (
+                SequenceInt::<T, BITS>::from(a),
+                SequenceInt::<T, BITS>::from(b),
+            );
+            let mut x = a;
+            x += b;
+            assert_eq!(x, a + b);
+            assert_eq!(x, a + b.0);
+            x -= b;
+            assert_eq!(x, a);
+            let mut y = a;
+            y -= b;
+            assert_eq!(y, a - b.0);
+        }
+    }
+    check::<u8, 8>(&[(250, 10), (0, 255), (5, 5)]);
+    check::<u16, 16>(&[(65530, 10), (3, 65535)]);
+    check::<u32, 24>(&[(16_777_210, 10), (1, 16_777_215)]);
+    check::<u32, 14>(&[(16_380, 10), (2, 16_383)]);
+    check::<u64, 64>(&[(u64::MAX, 2), (0, u64::MAX)]);
+
+    let mut a = SeqU8::from(250);
+    a += SeqU8::from(10);
+    assert_eq!(a, SeqU8::from(4));
+    a -= SeqU8::from(10);
+    assert_eq!(a, SeqU8::from(250));
+    // compare with the delta operator
+    assert_eq!(SeqU8::from(4) - SeqU8::from(250), SeqDelta::new(10));
+}
+
+#[test]
+fn test_newer_older() {
+    let origin = SeqU16::from(60_000);
+    let (a, b) = (SeqU16::from(65_530), SeqU16::from(40_000));
+    assert_eq!(a.cmp_from(b, origin), Ordering::Less);
+    assert_eq!(a.newer(b, origin), b);
+    assert_eq!(b.newer(a, origin), b);
+    assert_eq!(a.older(b, origin), a);
+    assert_eq!(b.older(a, origin), a);
+    assert_eq!(a.newer(a, origin), a);
+
+    // more than half-range apart, where `Ord` flips
+    assert!(a > b);
+    assert_eq!(Ord::max(a, b), a);
+
+    // a different origin changes the answer
+    let origin = SeqU16::from(30_000);
+    assert_eq!(a.newer(b, origin), a);
+    assert_eq!(a.older(b, origin), b);
+}
+
+#[test]
+fn test_rem_buckets() {
+    let buckets = [0u16, 7, 8, 9, 65535, 12345].map(|v| SeqU16::from(v) % 8);
+    assert_eq!(buckets, [0, 7, 0, 1, 7, 1]);
+
+    let mut counts = [0; 8];
+    for v in 0..64u16 {
+        counts[(SeqU16::from(v) % 8) as usize] += 1;
+    }
+    assert_eq!(counts, [8; 8]);
+
+    assert_eq!(SequenceInt::<u32, 14>::from(16_390) % 10, 6);
+}
+
+#[test]
+fn test_bit_ops() {
+    type S14 = SequenceInt<u32, 14>;
+
+    let mask = S14::modulus_mask();
+    let s = S14::from(0x2a5c);
+    assert_eq!((s ^ 0xffff_ffff).0, !0x2a5c & mask);
+    assert_eq!((s ^ 0xffff_ffff).0, 0x15a3);
+    assert_eq!(s ^ 0x2a5c, S14::from(0));
+    assert_eq!((s | 0xf_0000).0, 0x2a5c);
+    assert_eq!((s | u32::MAX).0, mask);
+    assert_eq!((s & 0xff).0, 0x5c);
+    for v in [0u32, 1, 0x1fff, 0x3fff, 0x7fff, u32::MAX] {
+        assert!((s ^ v).0 <= mask);
+    }
+    assert_eq!(SeqU8::from(0b1010) ^ 0b0110, SeqU8::from(0b1100));
+}
+
+#[test]
+fn test_add_usize() {
+    assert_eq!(SeqU16::from(65530).add_usize(10), SeqU16::from(4));
+    assert_eq!(SeqU8::from(250).add_usize(10), SeqU8::from(4));
+    // larger than u8 can hold: 300 = 256 + 44
+    assert_eq!(SeqU8::from(0).add_usize(300), SeqU8::from(44));
+    assert_eq!(SeqU8::from(200).add_usize(usize::MAX), SeqU8::from(199));
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(16_380).add_usize(10), S14::from(6));
+    assert_eq!(S14::from(0).add_usize((1 << 14) * 3 + 5), S14::from(5));
+
+    let base = SeqU16::from(65534);
+    let slots = ["a", "b", "c", "d"];
+    let seqs = (0..slots.len())
+        .map(|i| base.add_usize(i).0)
+        .collect::<Vec<_>>();
+    assert_eq!(seqs, [65534, 65535, 0, 1]);
+}
+
+#[test]
+fn test_in_half_open() {
+    let (start, end) = (SeqU32::from(u32::MAX - 4), SeqU32::from(10));
+    assert!(start.in_half_open(start, end));
+    assert!(SeqU32::from(u32::MAX).in_half_open(start, end));
+    assert!(SeqU32::from(0).in_half_open(start, end));
+    assert!(SeqU32::from(9).in_half_open(start, end));
+    assert!(!end.in_half_open(start, end));
+    assert!(!SeqU32::from(u32::MAX - 5).in_half_open(start, end));
+    assert!(!SeqU32::from(1000).in_half_open(start, end));
+
+    // empty span
+    assert!(!start.in_half_open(start, start));
+    assert!(!SeqU32::from(0).in_half_open(start, start));
+
+    type S14 = SequenceInt<u32, 14>;
+    assert!(S14::from(2).in_half_open(S14::from(16_000), S14::from(3)));
+    assert!(!S14::from(3).in_half_open(S14::from(16_000), S14::from(3)));
+}
+
+#[test]
+fn test_in_window() {
+    let start = SeqU16::from(65533);
+    assert!(start.in_window(start, 5));
+    assert!(SeqU16::from(1).in_window(start, 5));
+    assert!(!SeqU16::from(2).in_window(start, 5));
+    assert!(!SeqU16::from(65532).in_window(start, 5));
+    assert!(!start.in_window(start, 0));
+    // a window of the whole space but one
+    assert!(SeqU16::from(65531).in_window(start, u16::MAX));
+    assert!(!SeqU16::from(65532).in_window(start, u16::MAX));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_collect_in_window() {
+    let stream = [65530, 65534, 3, 0, 40_000, 65533, 2, 4, 65535, 1].map(SeqU16::from);
+    let kept = SeqU16::collect_in_window(stream, SeqU16::from(65533), 7);
+    assert_eq!(
+        kept.iter().map(|s| s.0).collect::<Vec<_>>(),
+        [65534, 3, 0, 65533, 2, 65535, 1]
+    );
+    assert!(SeqU16::collect_in_window(stream, SeqU16::from(100), 1000).is_empty());
+    assert!(SeqU16::collect_in_window(stream, SeqU16::from(0), 0).is_empty());
+}
+
+#[test]
+fn test_between_exclusive() {
+    let (lo, hi) = (SeqU16::from(65533), SeqU16::from(2));
+    for v in [65534, 65535, 0, 1] {
+        assert!(SeqU16::from(v).between_exclusive(lo, hi));
+    }
+    for v in [65533, 2, 3, 65532, 30_000] {
+        assert!(!SeqU16::from(v).between_exclusive(lo, hi));
+    }
+
+    // adjacent or equal endpoints leave nothing inside
+    let lo = SeqU16::from(65535);
+    assert!(!lo.between_exclusive(lo, SeqU16::from(0)));
+    assert!(!SeqU16::from(0).between_exclusive(lo, SeqU16::from(0)));
+    for v in [65535, 0, 100] {
+        assert!(!SeqU16::from(v).between_exclusive(lo, lo));
+    }
+    assert!(SeqU16::from(0).between_exclusive(lo, SeqU16::from(1)));
+}
+
+#[test]
+fn test_overflowing_add() {
+    assert_eq!(
+        SeqU8::from(250).overflowing_add(5),
+        (SeqU8::from(255), false)
+    );
+    assert_eq!(SeqU8::from(250).overflowing_add(6), (SeqU8::from(0), true));
+    assert_eq!(
+        SeqU64::from(u64::MAX).overflowing_add(1),
+        (SeqU64::from(0), true)
+    );
+    assert!(!SeqU64::from(0).overflowing_add(u64::MAX).1);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(
+        S14::from(16_380).overflowing_add(3),
+        (S14::from(16_383), false)
+    );
+    assert_eq!(S14::from(16_380).overflowing_add(4), (S14::from(0), true));
+    assert_eq!(
+        S14::from(0).overflowing_add(40_000),
+        (S14::from(7232), true)
+    );
+}
+
+#[test]
+fn test_wrapping_add_sub() {
+    fn check<T: UInt + TryFrom<u64>, const BITS: u8>() {
+        let mask = u64::MAX >> (64 - BITS as u32);
+        let mut state = 0x1234_5678_9abc_def0u64;
+        for _ in 0..1_000 {
+            let (Ok(a), Ok(b)) = (
+                T::try_from(next_rand(&mut state) & mask),
+                T::try_from(next_rand(&mut state) & mask),
+            ) else {
+                unreachable!()
+            };
+            let a = SequenceInt::<T, BITS>::from(a);
+            assert_eq!(a.wrapping_add(b), a + b);
+            assert_eq!(a.wrapping_sub(b), a - b);
+            assert_eq!(a.wrapping_add(b).wrapping_sub(b), a);
+        }
+    }
+    check::<u8, 8>();
+    check::<u16, 16>();
+    check::<u32, 24>();
+    check::<u64, 64>();
+    check::<u32, 14>();
+
+    assert_eq!(SeqU16::from(65530).wrapping_add(10), SeqU16::from(4));
+    assert_eq!(SeqU16::from(4).wrapping_sub(10), SeqU16::from(65530));
+}
+
+#[test]
+fn test_shift_in_space() {
+    type S14 = SequenceInt<u32, 14>;
+    let s = S14::from(0b11_0000_0000_0001);
+    assert_eq!(s.shl_in_space(1).0, 0b10_0000_0000_0010);
+    assert_eq!(s.shl_in_space(4).0, 0b1_0000);
+    assert_eq!(s.0 << 4, 0b11_0000_0000_0001_0000);
+    assert_eq!(s.shl_in_space(13).0, 1 << 13);
+    assert_eq!(s.shl_in_space(14).0, 0);
+    assert_eq!(s.shl_in_space(40).0, 0);
+    for by in 0..20 {
+        assert!(s.shl_in_space(by).0 <= S14::modulus_mask());
+    }
+
+    assert_eq!(s.shr_in_space(12).0, 0b11);
+    assert_eq!(s.shr_in_space(14).0, 0);
+    assert_eq!(SeqU8::from(0x81).shl_in_space(1), SeqU8::from(2));
+    assert_eq!(SeqU8::from(0x81).shr_in_space(7), SeqU8::from(1));
+}
+
+#[test]
+fn test_const_cmp() {
+    const LESS: Ordering =
+        SequenceInt::<u16, 16>::new_unchecked(65530).const_cmp(SequenceInt::new_unchecked(10));
+    const _: () = assert!(matches!(LESS, Ordering::Less));
+    const _: () = assert!(matches!(
+        SequenceInt::<u16, 16>::new_unchecked(1000).const_cmp(SequenceInt::new_unchecked(34000)),
+        Ordering::Greater
+    ));
+    const TABLE: [Ordering; 3] = {
+        let origin = SequenceInt::<u32, 24>::new_unchecked(16_777_210);
+        [
+            origin.const_cmp(SequenceInt::new_unchecked(5)),
+            origin.const_cmp(SequenceInt::new_unchecked(16_777_210)),
+            origin.const_cmp(SequenceInt::new_unchecked(16_777_200)),
+        ]
+    };
+    assert_eq!(TABLE, [Ordering::Less, Ordering::Equal, Ordering::Greater]);
+
+    let mut state = 0x0bad_5eed_u64;
+    for _ in 0..1_000 {
+        let (a, b) = (next_rand(&mut state), next_rand(&mut state));
+        let (a, b) = (SeqU16::from(a as u16), SeqU16::from(b as u16));
+        assert_eq!(a.const_cmp(b), a.cmp(&b));
+        let (c, d) = (
+            SequenceInt::<u32, 14>::from(a.0 as u32),
+            SequenceInt::<u32, 14>::from(b.0 as u32),
+        );
+        assert_eq!(c.const_cmp(d), c.cmp(&d));
+        let (e, f) = (
+            SeqU64::from(a.0 as u64 * 0x1_0001_0001),
+            SeqU64::from(b.0 as u64 * 0x1_0001_0001),
+        );
+        assert_eq!(e.const_cmp(f), e.cmp(&f));
+    }
+}
+
+#[test]
+fn test_gap_from_expected() {
+    let expected = SeqU16::from(65534);
+    assert_eq!(expected.gap_from_expected(expected), 0);
+    assert_eq!(SeqU16::from(65535).gap_from_expected(expected), 1);
+    assert_eq!(SeqU16::from(3).gap_from_expected(expected), 5);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(2).gap_from_expected(S14::from(16_382)), 4);
+    assert_eq!(
+        S14::from(16_381).gap_from_expected(S14::from(16_382)),
+        16_383
+    );
+}
+
+#[test]
+fn test_tie_break_policy() {
+    type Greater = SequenceInt<u8, 8>;
+    type Less = SequenceInt<u8, 8, LessWins>;
+
+    let (a, b) = (Greater::from(0), Greater::from(128));
+    assert!(a > b && b > a);
+    assert_eq!(a.cmp(&b), Ordering::Greater);
+    assert_eq!(a.const_cmp(b), Ordering::Greater);
+
+    let (a, b) = (Less::from(0), Less::from(128));
+    assert!(a < b && b < a);
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert_eq!(b.cmp(&a), Ordering::Less);
+    assert_eq!(a.const_cmp(b), Ordering::Less);
+
+    // everywhere else the policies agree
+    for x in 0..=u8::MAX {
+        for y in [0u8, 1, 127, 129, 200] {
+            if x.wrapping_sub(y) != 128 {
+                assert_eq!(
+                    Less::from(x).cmp(&Less::from(y)),
+                    Greater::from(x).cmp(&Greater::from(y))
+                );
+            }
+        }
+    }
+
+    type Less24 = SequenceInt<u32, 24, LessWins>;
+    let (a, b) = (Less24::from(5), Less24::from(5 + (1 << 23)));
+    assert!(a < b && b < a);
+    assert_eq!(a + 1u32, Less24::from(6));
+    assert_eq!(format!("{a:?}"), "SequenceInt<24>(5)");
+}
+
+#[test]
+fn test_inc_dec_by() {
+    let mut x = SeqU8::from(250);
+    x.inc_by(10);
+    assert_eq!(x, SeqU8::from(4));
+    x.dec_by(10);
+    assert_eq!(x, SeqU8::from(250));
+    x.inc_by(0);
+    assert_eq!(x, SeqU8::from(250));
+
+    let mut y = SequenceInt::<u32, 14>::from(16_380);
+    y.inc_by(1 << 20); // a whole number of wraps
+    assert_eq!(y.0, 16_380);
+    y.inc_by(5);
+    assert_eq!(y.0, 1);
+    y.dec_by(2);
+    assert_eq!(y.0, 16_383);
+}
+
+#[test]
+fn test_progress() {
+    let start = SeqU16::from(65000);
+    assert_eq!(start.progress(start, 1000), 0.0);
+    assert_eq!(SeqU16::from(65500).progress(start, 1000), 0.5);
+    assert_eq!(SeqU16::from(464).progress(start, 1000), 1.0);
+    assert_eq!(SeqU16::from(2000).progress(start, 1000), 1.0);
+    assert_eq!(SeqU16::from(5).progress(SeqU16::from(5), 0), 1.0);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(1).progress(S14::from(16_381), 16), 0.25);
+    assert_eq!(
+        SeqU64::from(u64::MAX).progress(SeqU64::from(0), u64::MAX),
+        1.0
+    );
+}
+
+#[test]
+fn test_hash_matches_eq_after_masking() {
+    use std::hash::{BuildHasher, RandomState};
+
+    fn check<const BITS: u8>(state: &mut u64) {
+        let hasher = RandomState::new();
+        let modulus = 1u32 << BITS;
+        for _ in 0..1000 {
+            let n = next_rand(state) as u32 % modulus;
+            let wrapped = n + modulus * (1 + next_rand(state) as u32 % 100);
+            let a = SequenceInt::<u32, BITS>::from(n);
+            let paths = [
+                SequenceInt::from(wrapped),
+                SequenceInt::from(0) + wrapped,
+                SequenceInt::from(modulus - 1).wrapping_add(wrapped.wrapping_add(1)),
+                {
+                    let mut b = SequenceInt::from(0);
+                    b.inc_by(wrapped);
+                    b
+                },
+                // stored unmasked, so only equal once masked
+                SequenceInt::new_unchecked(wrapped),
+            ];
+            for b in paths {
+                assert_eq!(a, b);
+                assert_eq!(hasher.hash_one(a), hasher.hash_one(b));
+            }
+        }
+    }
+
+    let mut state = 0x4a5b_u64;
+    check::<24>(&mut state);
+    check::<14>(&mut state);
+}
+
+#[test]
+fn test_signed_offset_from() {
+    let origin = SeqU16::from(1);
+    assert_eq!(SeqU16::from(65535).signed_offset_from(origin), -2i16);
+    assert_eq!(SeqU16::from(4).signed_offset_from(origin), 3);
+    assert_eq!(origin.signed_offset_from(origin), 0);
+    assert_eq!(SeqU16::from(32_769).signed_offset_from(origin), i16::MIN);
+
+    assert_eq!(SeqU8::from(0).signed_offset_from(SeqU8::from(130)), 126i8);
+    assert_eq!(
+        SeqU64::from(0).signed_offset_from(SeqU64::from(u64::MAX)),
+        1i64
+    );
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(16_380).signed_offset_from(S14::from(2)), -6i32);
+}
+
+#[test]
+fn test_midpoint_direction() {
+    // 60000 steps forward from 1000, 5536 backward
+    let (a, b) = (SeqU16::from(1000), SeqU16::from(61000));
+    assert_eq!(a.midpoint_forward(b), SeqU16::from(31000));
+    assert_eq!(a.midpoint_backward(b), SeqU16::from(63768));
+    assert_eq!(b.midpoint_forward(a), SeqU16::from(63768));
+    assert_eq!(b.midpoint_backward(a), SeqU16::from(31000));
+    assert_eq!(a.midpoint_forward(a), a);
+    assert_eq!(a.midpoint_forward(a.next()), a);
+    assert_eq!(a.midpoint_backward(a.prev()), a);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(
+        S14::from(16_000).midpoint_forward(S14::from(10)),
+        S14::from(16_197)
+    );
+    assert_eq!(
+        S14::from(16_000).midpoint_backward(S14::from(10)),
+        S14::from(8005)
+    );
+}
+
+#[test]
+fn test_zero_extend() {
+    let narrow = SequenceInt::<u32, 14>::from(16_383);
+    let wide = narrow.zero_extend::<24>();
+    assert_eq!(wide, SeqU24::from(16_383));
+    assert_eq!(wide.next(), SeqU24::from(16_384));
+    assert_eq!(narrow.next().zero_extend::<24>(), SeqU24::from(0));
+    assert_eq!(narrow.zero_extend::<14>(), narrow);
+    assert_eq!(
+        SequenceInt::<u64, 20>::from(7).zero_extend::<64>(),
+        SeqU64::from(7)
+    );
+}
+
+#[test]
+fn test_cmp_within() {
+    let a = SeqU16::from(65530);
+    assert_eq!(a.cmp_within(SeqU16::from(10), 100), Ok(Ordering::Less));
+    assert_eq!(SeqU16::from(10).cmp_within(a, 100), Ok(Ordering::Greater));
+    assert_eq!(a.cmp_within(a, 0), Ok(Ordering::Equal));
+    assert_eq!(a.cmp_within(SeqU16::from(10), 16), Ok(Ordering::Less));
+    assert_eq!(
+        a.cmp_within(SeqU16::from(10), 15),
+        Err(OutOfWindow {
+            distance: 16,
+            window: 15
+        })
+    );
+    // in order by the half-range rule, but far outside the window
+    assert_eq!(
+        a.cmp_within(SeqU16::from(20_000), 1000),
+        Err(OutOfWindow {
+            distance: 20_006,
+            window: 1000
+        })
+    );
+    assert_eq!(
+        a.cmp_within(SeqU16::from(20_000), u16::MAX),
+        Ok(Ordering::Less)
+    );
+}
+
+#[test]
+fn test_bit_counts() {
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(1 << 13).leading_zeros_in_bits(), 0);
+    assert_eq!(S14::from(1 << 13).count_ones(), 1);
+    assert_eq!(S14::from(1).leading_zeros_in_bits(), 13);
+    assert_eq!(S14::from(0).leading_zeros_in_bits(), 14);
+    assert_eq!(S14::from(u32::MAX).count_ones(), 14);
+
+    assert_eq!(SeqU24::from(0x00ff_00f0).leading_zeros_in_bits(), 0);
+    assert_eq!(SeqU24::from(0x00ff_00f0).count_ones(), 12);
+    assert_eq!(SeqU24::from(0x0000_0100).leading_zeros_in_bits(), 15);
+    assert_eq!(SeqU64::from(1).leading_zeros_in_bits(), 63);
+}
+
+#[test]
+fn test_checked_sub_self() {
+    let base = SeqU16::from(100);
+    assert_eq!(
+        (base + 32_767u16).checked_sub_self(base),
+        Some(SeqDelta::new(32_767))
+    );
+    assert_eq!((base + 32_768u16).checked_sub_self(base), None);
+    assert_eq!(base.checked_sub_self(base + 32_768u16), None);
+    assert_eq!(
+        (base + 32_769u16).checked_sub_self(base),
+        Some(SeqDelta::new(-32_767))
+    );
+    assert_eq!(base.checked_sub_self(base), Some(SeqDelta::new(0)));
+    assert_eq!(
+        SeqU16::from(2).checked_sub_self(SeqU16::from(65534)),
+        Some(SeqU16::from(2) - SeqU16::from(65534))
+    );
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(8192).checked_sub_self(S14::from(0)), None);
+    assert_eq!(
+        S14::from(8191).checked_sub_self(S14::from(0)),
+        Some(SeqDelta::new(8191))
+    );
+    assert_eq!(
+        SeqU64::from(1 << 63).checked_sub_self(SeqU64::from(0)),
+        None
+    );
+}
+
+#[test]
+fn test_advance_by_duration() {
+    use core::time::Duration;
+
+    let ts = SeqU32::from(u32::MAX - 1000);
+    assert_eq!(
+        ts.advance_by_duration(Duration::from_millis(33), 90_000),
+        SeqU32::from(1969)
+    );
+    assert_eq!(ts.advance_by_duration(Duration::ZERO, 90_000), ts);
+    // 15.99998 ticks round to 16, and exactly 16.5 rounds up
+    assert_eq!(
+        SeqU32::from(0).advance_by_duration(Duration::from_nanos(333_333), 48_000),
+        SeqU32::from(16)
+    );
+    assert_eq!(
+        SeqU32::from(0).advance_by_duration(Duration::from_micros(343_750), 48),
+        SeqU32::from(17)
+    );
+    assert_eq!(
+        SeqU64::from(5).advance_by_duration(Duration::MAX, u32::MAX),
+        SeqU64::from(4)
+    );
+}
+
+#[test]
+fn test_full_width_cmp_matches_generic() {
+    fn check<P: TieBreak>(state: &mut u64) {
+        type S<P> = SequenceInt<u16, 16, P>;
+        let mut firsts = [0u16, 1, 0x7fff, 0x8000, 0xffff].to_vec();
+        firsts.extend((0..11).map(|_| next_rand(state) as u16));
+        for a in firsts.into_iter().map(S::<P>::from) {
+            for b in (0..=u16::MAX).map(S::<P>::from) {
+                assert_eq!(a.cmp(&b), S::<P>::ordering_of(a.diff(b)), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    let mut state = 0xf00d_u64;
+    check::<GreaterWins>(&mut state);
+    check::<LessWins>(&mut state);
+    for _ in 0..10_000 {
+        let (a, b) = (
+            SeqU64::from(next_rand(&mut state)),
+            SeqU64::from(next_rand(&mut state)),
+        );
+        assert_eq!(a.cmp(&b), SeqU64::ordering_of(a.diff(b)));
+    }
+}
+
+#[test]
+fn test_is_zero_is_max() {
+    assert!(SeqU24::from(0).is_zero() && !SeqU24::from(0).is_max());
+    assert!(SeqU24::from(0xff_ffff).is_max() && !SeqU24::from(0xff_ffff).is_zero());
+    assert!(SeqU24::from(0xff_ffff).next().is_zero());
+    assert!(SeqU24::from(1 << 24).is_zero());
+    assert!(!SeqU24::from(0xff_fffe).is_max());
+    assert!(SeqU64::from(u64::MAX).is_max());
+
+    // stored over-range, but judged by the masked value like `get`
+    let max = SeqU24::new_unchecked(0x1ff_ffff);
+    assert!(max.is_max() && max.get() == SeqU24::modulus_mask());
+    assert!(SeqU24::new_unchecked(1 << 24).is_zero());
+}
+
+#[test]
+fn test_approx_eq() {
+    let a = SeqU16::from(65533);
+    assert!(a.approx_eq(a, 0));
+    assert!(a.approx_eq(SeqU16::from(2), 5));
+    assert!(SeqU16::from(2).approx_eq(a, 5));
+    assert!(!a.approx_eq(SeqU16::from(3), 5));
+    assert!(a.approx_eq(SeqU16::from(65528), 5));
+    assert!(!a.approx_eq(SeqU16::from(65527), 5));
+    assert!(!a.approx_eq(SeqU16::from(65534), 0));
+
+    type S14 = SequenceInt<u32, 14>;
+    assert!(S14::from(16_380).approx_eq(S14::from(3), 7));
+    assert!(!S14::from(16_380).approx_eq(S14::from(4), 7));
+}
+
+#[test]
+fn test_nearest() {
+    let reference = SeqU16::from(65530);
+    assert_eq!(reference.abs_diff(SeqU16::from(2)), 8);
+    assert_eq!(SeqU16::from(2).abs_diff(reference), 8);
+    assert_eq!(SeqU16::from(0).abs_diff(SeqU16::from(32_768)), 32_768);
+
+    // 2 is numerically far from 65530 but only 8 steps away across the wrap
+    let candidates = [10_000, 65_000, 2, 60_000].map(SeqU16::from);
+    assert_eq!(reference.nearest(&candidates), Some(SeqU16::from(2)));
+    assert_eq!(
+        SeqU16::from(64_000).nearest(&candidates),
+        Some(SeqU16::from(65_000))
+    );
+    assert_eq!(reference.nearest(&[]), None);
+
+    type S14 = SequenceInt<u32, 14>;
+    let candidates = [8000, 16_000, 10].map(S14::from);
+    assert_eq!(S14::from(16_380).nearest(&candidates), Some(S14::from(10)));
+}
+
+#[test]
+fn test_modulus() {
+    assert_eq!(SeqU64::modulus(), 1 << 64);
+    assert_eq!(SeqU64::MODULUS_U128, u64::MAX as u128 + 1);
+    assert_eq!(SeqU24::modulus(), 16_777_216);
+    assert_eq!(SeqU24::modulus(), SeqU24::modulus_mask() as u128 + 1);
+    assert_eq!(SequenceInt::<u8, 1>::modulus(), 2);
+    const M: u128 = SeqU16::modulus();
+    assert_eq!(M, 65_536);
+}
+
+#[test]
+fn test_to_u128() {
+    assert_eq!(SeqU8::from(255).to_u128(), 255);
+    assert_eq!(SeqU32::from(u32::MAX).to_u128(), u32::MAX as u128);
+    assert_eq!(SeqU64::from(u64::MAX).to_u128(), u64::MAX as u128);
+    assert_eq!(SeqU24::from(u32::MAX).to_u128(), 0xff_ffff);
+    assert_eq!(
+        SequenceInt::<u32, 14>::new_unchecked(1 << 14 | 5).to_u128(),
+        5
+    );
+}
+
+#[test]
+fn test_checked_next_prev() {
+    assert_eq!(SeqU8::from(254).checked_next(), Some(SeqU8::from(255)));
+    assert_eq!(SeqU8::from(255).checked_next(), None);
+    assert_eq!(SeqU8::from(1).checked_prev(), Some(SeqU8::from(0)));
+    assert_eq!(SeqU8::from(0).checked_prev(), None);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(16_383).checked_next(), None);
+    assert_eq!(S14::from(16_383).checked_prev(), Some(S14::from(16_382)));
+    assert_eq!(S14::from(0).checked_prev(), None);
+    assert_eq!(S14::from(0).checked_next(), Some(S14::from(1)));
+}
+
+#[test]
+fn test_with_value() {
+    type S14 = SequenceInt<u32, 14, LessWins>;
+    let s = S14::from(5);
+    assert_eq!(s.with_value(9), S14::from(9));
+    assert_eq!(s.with_value(16_384 + 3).0, 3);
+    assert_eq!(s.with_value(u32::MAX).0, 16_383);
+}
+
+#[test]
+fn test_bisect() {
+    // first value not yet acknowledged, with the arc crossing the wrap
+    let acked = SeqU16::from(3);
+    let start = SeqU16::from(65000);
+    let end = SeqU16::from(1000);
+    assert_eq!(start.bisect(end, |s| acked.covers(s)), SeqU16::from(4));
+    assert_eq!(start.bisect(end, |_| true), end);
+    assert_eq!(start.bisect(end, |_| false), start);
+    assert_eq!(start.bisect(start, |_| true), start);
+
+    type S14 = SequenceInt<u32, 14>;
+    let (a, b) = (S14::from(16_000), S14::from(7000));
+    assert_eq!(a.bisect(b, |s| a.forward_distance(s) < 500), S14::from(116));
+
+    let caught = std::panic::catch_unwind(|| a.bisect(S14::from(8000), |_| true));
+    assert!(caught.is_err());
+}
+
+#[test]
+fn test_as_ref() {
+    fn raw(v: impl AsRef<u32>) -> u32 {
+        *v.as_ref()
+    }
+
+    assert_eq!(raw(SeqU32::from(u32::MAX)), u32::MAX);
+    assert_eq!(raw(SeqU24::from(1 << 24 | 9)), 9);
+}
+
+#[test]
+fn test_steps_between() {
+    let (a, b) = (SeqU16::from(65530), SeqU16::from(4));
+    assert_eq!(SeqU16::steps_between(a, b), 10);
+    assert_eq!(SeqU16::steps_between(b, a), 65526);
+    assert_eq!(SeqU16::steps_between(a, a), 0);
+    assert_eq!(SeqU16::saturating_steps_between(a, b), 10);
+    assert_eq!(SeqU16::saturating_steps_between(a, a.prev()), u16::MAX);
+
+    type S14 = SequenceInt<u32, 14>;
+    let wide = S14::new_unchecked(u32::MAX);
+    assert_eq!(S14::saturating_steps_between(S14::from(0), wide), 16_383);
+    assert_eq!(S14::steps_between(S14::from(0), wide), 16_383);
+    assert_eq!(S14::steps_between(S14::from(16_380), S14::from(2)), 6);
+}
+
+#[test]
+fn test_quadrant() {
+    use Quadrant::*;
+
+    let origin = SeqU8::from(200);
+    let at = |v: u8| SeqU8::from(v).quadrant(origin);
+    assert_eq!(at(200), NearAhead);
+    assert_eq!(at(7), NearAhead);
+    assert_eq!(at(8), FarAhead);
+    assert_eq!(at(71), FarAhead);
+    assert_eq!(at(72), FarBehind);
+    assert_eq!(at(135), FarBehind);
+    assert_eq!(at(136), NearBehind);
+    assert_eq!(at(199), NearBehind);
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(S14::from(16_383).quadrant(S14::from(0)), NearBehind);
+    assert_eq!(S14::from(4096).quadrant(S14::from(0)), FarAhead);
+
+    // too few values for four quadrants; the half-range one is far behind
+    type S1 = SequenceInt<u8, 1>;
+    assert_eq!(S1::from(1).quadrant(S1::from(1)), NearAhead);
+    assert_eq!(S1::from(0).quadrant(S1::from(1)), FarBehind);
+    type S2 = SequenceInt<u8, 2>;
+    let quadrants = (0..4).map(|v| S2::from(v).quadrant(S2::from(0)));
+    assert!(quadrants.eq([NearAhead, FarAhead, FarBehind, NearBehind]));
+}
+
+#[test]
+fn test_circular_clamp() {
+    let center = SeqU16::from(65530);
+    assert_eq!(
+        SeqU16::from(65533).circular_clamp(center, 10),
+        SeqU16::from(65533)
+    );
+    assert_eq!(SeqU16::from(4).circular_clamp(center, 10), SeqU16::from(4));
+    assert_eq!(SeqU16::from(5).circular_clamp(center, 10), SeqU16::from(4));
+    assert_eq!(
+        SeqU16::from(20_000).circular_clamp(center, 10),
+        SeqU16::from(4)
+    );
+    assert_eq!(
+        SeqU16::from(65500).circular_clamp(center, 10),
+        SeqU16::from(65520)
+    );
+    assert_eq!(
+        SeqU16::from(40_000).circular_clamp(center, 10),
+        SeqU16::from(65520)
+    );
+    assert_eq!(SeqU16::from(40_000).circular_clamp(center, 0), center);
+    assert_eq!(
+        SeqU16::from(40_000).circular_clamp(center, 32_768),
+        SeqU16::from(40_000)
+    );
+
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(
+        S14::from(16_000).circular_clamp(S14::from(5), 100),
+        S14::from(16_289)
+    );
+}
+
+#[test]
+fn test_abs_cmp() {
+    let (a, b) = (SeqU16::from(65530), SeqU16::from(3));
+    assert_eq!(a.cmp(&b), Ordering::Less);
+    assert_eq!(a.abs_cmp(b.to_u128()), Ordering::Greater);
+    assert_eq!(b.abs_cmp(a.to_u128()), Ordering::Less);
+    assert_eq!(a.abs_cmp(65530), Ordering::Equal);
+
+    // across widths
+    let narrow = SequenceInt::<u32, 14>::from(16_000);
+    assert_eq!(
+        narrow.abs_cmp(SeqU64::from(u64::MAX).to_u128()),
+        Ordering::Less
+    );
+    assert_eq!(
+        SeqU64::from(u64::MAX).abs_cmp(narrow.to_u128()),
+        Ordering::Greater
+    );
+    assert_eq!(SeqU64::from(u64::MAX).abs_cmp(1 << 64), Ordering::Less);
+}
+
+#[test]
+fn test_add_offset_beyond_modulus() {
+    type S8 = SequenceInt<u32, 8>;
+    assert_eq!(S8::from(200) + 100u32, S8::from(44));
+    assert_eq!(S8::from(200) + 256u32, S8::from(200));
+    assert_eq!(S8::from(200) - 300u32, S8::from(156));
+
+    // offsets that also overflow the storage type
+    let mut state = 0x0ff5e7_u64;
+    for _ in 0..1000 {
+        let (a, rhs) = (next_rand(&mut state) as u32, next_rand(&mut state) as u32);
+        let expected = (a as u64 + rhs as u64) % 256;
+        assert_eq!((S8::from(a) + rhs).0 as u64, expected);
+        let expected = (a as u64 + (1 << 32) - rhs as u64) % 256;
+        assert_eq!((S8::from(a) - rhs).0 as u64, expected);
+        assert_eq!(S8::from(a).wrapping_add(rhs), S8::from(a) + rhs);
+    }
+}
+
+#[test]
+fn test_get_raw_storage() {
+    type S14 = SequenceInt<u32, 14>;
+    let s = S14::from(16_390);
+    assert_eq!((s.get(), s.raw_storage()), (6, 6));
+
+    let unchecked = S14::new_unchecked(16_390);
+    assert_eq!(unchecked.raw_storage(), 16_390);
+    assert_eq!(unchecked.get(), 6);
+    assert_eq!(SeqU32::new_unchecked(u32::MAX).get(), u32::MAX);
+}
+
+#[test]
+fn test_advance_to() {
+    let mut high = SeqU16::from(65530);
+    assert!(!high.advance_to(SeqU16::from(65000)));
+    assert!(!high.advance_to(high));
+    assert_eq!(high, SeqU16::from(65530));
+    assert!(high.advance_to(SeqU16::from(4)));
+    assert_eq!(high, SeqU16::from(4));
+    assert!(!high.advance_to(SeqU16::from(65535)));
+
+    let mut x = SeqU8::from(0);
+    assert!(!x.advance_to(SeqU8::from(128)));
+    assert!(x.advance_to(SeqU8::from(127)));
+}
+
+#[test]
+fn test_lerp() {
+    let (a, b) = (SeqU16::from(65000), SeqU16::from(1000));
+    assert_eq!(a.lerp(b, 1, 4), SeqU16::from(65384));
+    assert_eq!(a.lerp(b, 3, 4), SeqU16::from(616));
+    assert_eq!(a.lerp(b, 0, 4), a);
+    assert_eq!(a.lerp(b, 4, 4), b);
+    // 1536 / 3 = 512, and 1536 * 5 / 7 = 1097.14 rounds down
+    assert_eq!(a.lerp(b, 1, 3), SeqU16::from(65512));
+    assert_eq!(a.lerp(b, 5, 7), SeqU16::from(561));
+    assert_eq!(
+        SeqU64::from(0)
+            .lerp(SeqU64::from(u64::MAX), u64::MAX, u64::MAX)
+            .0,
+        u64::MAX
+    );
+
+    assert!(std::panic::catch_unwind(|| a.lerp(b, 1, 0)).is_err());
 }
@@ -0,0 +1,276 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use crate::{OutOfRange, SequenceInt, TieBreak, UInt};
+
+/// Error returned when parsing a [`SequenceInt`] from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSeqError {
+    /// There were no digits (after any sign or radix prefix).
+    Empty,
+    /// The byte at `position` of the input is not a digit in the radix.
+    InvalidDigit { position: usize },
+    /// The number doesn't fit in the sequence space.
+    OutOfRange(OutOfRange),
+    /// The number is too large for the storage type, or for a `u128` at all,
+    /// so can't be masked or reported either.
+    Overflow,
+}
+
+impl fmt::Display for ParseSeqError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("cannot parse sequence number from empty string"),
+            Self::InvalidDigit { position } => {
+                write!(f, "invalid digit at position {position} in sequence number")
+            }
+            Self::OutOfRange(e) => write!(f, "sequence number {e}"),
+            Self::Overflow => f.write_str("sequence number is too large"),
+        }
+    }
+}
+
+impl core::error::Error for ParseSeqError {}
+
+// `0x`/`0o`/`0b` prefixes are accepted when they agree with the radix
+fn strip_radix_prefix(s: &str, radix: u32) -> &str {
+    let prefix = match radix {
+        16 => ["0x", "0X"],
+        8 => ["0o", "0O"],
+        2 => ["0b", "0B"],
+        _ => return s,
+    };
+    prefix.iter().find_map(|p| s.strip_prefix(p)).unwrap_or(s)
+}
+
+// Parses an optionally `+`-signed, optionally prefixed number, stopping at
+// the first digit that overflows a `u128`
+fn parse_u128(s: &str, radix: u32) -> Result<u128, ParseSeqError> {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be in 2..=36, got {radix}"
+    );
+    let unsigned = s.strip_prefix('+').unwrap_or(s);
+    let digits = strip_radix_prefix(unsigned, radix);
+    if digits.is_empty() {
+        return Err(ParseSeqError::Empty);
+    }
+    let offset = s.len() - digits.len();
+    digits
+        .char_indices()
+        .try_fold(0u128, |acc, (i, c)| match c.to_digit(radix) {
+            Some(d) => acc
+                .checked_mul(radix as u128)
+                .and_then(|acc| acc.checked_add(d as u128))
+                .ok_or(ParseSeqError::Overflow),
+            None => Err(ParseSeqError::InvalidDigit {
+                position: offset + i,
+            }),
+        })
+}
+
+impl<T, const BITS: u8, P> SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    /// Parses the inner value in the given `radix` and masks it into the
+    /// sequence space, like `From<T>` does.
+    ///
+    /// A leading `+` is allowed, and a `0x`, `0o` or `0b` prefix is skipped
+    /// for radix 16, 8 and 2. The value has to fit the storage type, otherwise
+    /// [`ParseSeqError::Overflow`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in the range `2..=36`.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseSeqError> {
+        let value = parse_u128(s, radix)?;
+        if value > T::MAX.to_u64() as u128 {
+            return Err(ParseSeqError::Overflow);
+        }
+        Ok(Self::from(T::truncate_from_u64(value as u64)))
+    }
+
+    /// Formats the inner value in the given `radix`, without any prefix and
+    /// using lowercase digits.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in the range `2..=36`.
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn to_string_radix(self, radix: u32) -> alloc::string::String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in 2..=36, got {radix}"
+        );
+        let radix = radix as u64;
+        let mut v = self.0.to_u64();
+        // 64 binary digits is the longest possible output
+        let mut buf = [0u8; 64];
+        let mut i = buf.len();
+        loop {
+            i -= 1;
+            let d = (v % radix) as u8;
+            buf[i] = if d < 10 { b'0' + d } else { b'a' + d - 10 };
+            v /= radix;
+            if v == 0 {
+                break;
+            }
+        }
+        buf[i..].iter().map(|&b| b as char).collect()
+    }
+}
+
+/// Parses a decimal value like [`SequenceInt::from_str_radix`], except that
+/// values that don't fit in `BITS` bits are rejected rather than masked.
+impl<T, const BITS: u8, P> FromStr for SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Err = ParseSeqError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = parse_u128(s, 10)?;
+        if value > Self::modulus_mask().to_u64() as u128 {
+            return Err(ParseSeqError::OutOfRange(OutOfRange {
+                value,
+                bits: BITS,
+                storage_bits: T::BITS,
+            }));
+        }
+        Ok(Self(T::truncate_from_u64(value as u64), PhantomData))
+    }
+}
+
+#[test]
+fn test_from_str_radix() {
+    use crate::{SeqU16, SeqU24};
+
+    assert_eq!(
+        SeqU16::from_str_radix("0xABCD", 16),
+        Ok(SeqU16::from(0xabcd))
+    );
+    assert_eq!(
+        SeqU16::from_str_radix("0Xabcd", 16),
+        Ok(SeqU16::from(0xabcd))
+    );
+    assert_eq!(SeqU16::from_str_radix("abcd", 16), Ok(SeqU16::from(0xabcd)));
+    assert_eq!(SeqU16::from_str_radix("0b101", 2), Ok(SeqU16::from(5)));
+    assert_eq!(SeqU16::from_str_radix("777", 8), Ok(SeqU16::from(0o777)));
+    assert!(SeqU16::from_str_radix("0xABCDE", 16).is_err());
+    assert!(SeqU16::from_str_radix("xyz", 16).is_err());
+    assert!(SeqU16::from_str_radix("", 16).is_err());
+
+    // fits the storage but not the 24-bit space, so is masked
+    assert_eq!(SeqU24::from_str_radix("0x1000005", 16), Ok(SeqU24::from(5)));
+    assert_eq!(
+        SeqU24::from_str_radix("0xffffff", 16),
+        Ok(SeqU24::from(0xff_ffff))
+    );
+}
+
+#[test]
+fn test_parse_errors() {
+    use crate::{SeqU16, SeqU24, SeqU64};
+
+    assert_eq!("1234".parse(), Ok(SeqU16::from(1234)));
+    assert_eq!("+7".parse(), Ok(SeqU16::from(7)));
+    assert_eq!("18446744073709551615".parse(), Ok(SeqU64::from(u64::MAX)));
+
+    assert_eq!("".parse::<SeqU16>(), Err(ParseSeqError::Empty));
+    assert_eq!("+".parse::<SeqU16>(), Err(ParseSeqError::Empty));
+    assert_eq!(SeqU16::from_str_radix("0x", 16), Err(ParseSeqError::Empty));
+
+    assert_eq!(
+        "12a4".parse::<SeqU16>(),
+        Err(ParseSeqError::InvalidDigit { position: 2 })
+    );
+    assert_eq!(
+        "-1".parse::<SeqU16>(),
+        Err(ParseSeqError::InvalidDigit { position: 0 })
+    );
+    assert_eq!(
+        " 1".parse::<SeqU16>(),
+        Err(ParseSeqError::InvalidDigit { position: 0 })
+    );
+    // positions count the prefix
+    assert_eq!(
+        SeqU16::from_str_radix("+0xag", 16),
+        Err(ParseSeqError::InvalidDigit { position: 4 })
+    );
+
+    assert_eq!(
+        "16777216".parse::<SeqU24>(),
+        Err(ParseSeqError::OutOfRange(OutOfRange {
+            value: 16_777_216,
+            bits: 24,
+            storage_bits: 32
+        }))
+    );
+    assert_eq!(
+        "18446744073709551616".parse::<SeqU64>(),
+        Err(ParseSeqError::OutOfRange(OutOfRange {
+            value: 1 << 64,
+            bits: 64,
+            storage_bits: 64
+        }))
+    );
+    assert_eq!(
+        "9".repeat(50).parse::<SeqU16>(),
+        Err(ParseSeqError::Overflow)
+    );
+    assert_eq!(
+        SeqU16::from_str_radix(&"f".repeat(33), 16),
+        Err(ParseSeqError::Overflow)
+    );
+    assert_eq!(
+        SeqU16::from_str_radix("0x10000", 16),
+        Err(ParseSeqError::Overflow)
+    );
+    // the largest `u128` still parses, and is reported as is
+    assert_eq!(
+        u128::MAX.to_string().parse::<SeqU64>(),
+        Err(ParseSeqError::OutOfRange(OutOfRange {
+            value: u128::MAX,
+            bits: 64,
+            storage_bits: 64
+        }))
+    );
+
+    assert_eq!(
+        ParseSeqError::Overflow.to_string(),
+        "sequence number is too large"
+    );
+    assert_eq!(
+        ParseSeqError::InvalidDigit { position: 2 }.to_string(),
+        "invalid digit at position 2 in sequence number"
+    );
+    assert_eq!(
+        ParseSeqError::OutOfRange(OutOfRange {
+            value: 16_777_216,
+            bits: 24,
+            storage_bits: 32
+        })
+        .to_string(),
+        "sequence number 16777216 is out of range for 24 bits"
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_to_string_radix() {
+    use crate::SeqU16;
+
+    assert_eq!(SeqU16::from(0xabcd).to_string_radix(16), "abcd");
+    assert_eq!(SeqU16::from(5).to_string_radix(2), "101");
+    assert_eq!(SeqU16::from(0).to_string_radix(16), "0");
+    assert_eq!(SeqU16::from(u16::MAX).to_string_radix(36), "1ekf");
+    for v in [0u16, 1, 0x7fff, 0xffff] {
+        let s = SeqU16::from(v);
+        assert_eq!(SeqU16::from_str_radix(&s.to_string_radix(16), 16), Ok(s));
+    }
+}
@@ -0,0 +1,511 @@
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::{GapTooLarge, GreaterWins, SequenceInt, TieBreak, UInt};
+
+/// Half-open span `[start, end)` of sequence numbers, following the forward
+/// arc from `start`.
+///
+/// `start == end` is the empty range, so a range can cover at most
+/// `2^BITS - 1` values.
+#[derive(Copy, Clone, Debug)]
+pub struct SeqRange<T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    start: SequenceInt<T, BITS, P>,
+    end: SequenceInt<T, BITS, P>,
+}
+
+impl<T, const BITS: u8, P> SeqRange<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    pub fn new(start: SequenceInt<T, BITS, P>, end: SequenceInt<T, BITS, P>) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn start(&self) -> SequenceInt<T, BITS, P> {
+        self.start
+    }
+
+    /// One past the last value in the range.
+    #[must_use]
+    #[inline]
+    pub fn end(&self) -> SequenceInt<T, BITS, P> {
+        self.end
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> T {
+        self.start.forward_distance(self.end)
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, seq: SequenceInt<T, BITS, P>) -> bool {
+        self.start.forward_distance(seq) < self.len()
+    }
+
+    /// Returns the equivalent `start..end` over the raw values, or `None` if the
+    /// range crosses the wrap boundary.
+    ///
+    /// A range ending exactly at the top of the space (`end == 0`) is still
+    /// representable for narrow widths, as `start..2^BITS`, but not for
+    /// full-width ones.
+    #[must_use]
+    pub fn as_std_range(&self) -> Option<Range<T>> {
+        let (start, end) = (self.start.0, self.end.0);
+        if start <= end {
+            Some(start..end)
+        } else if end == T::ZERO && !SequenceInt::<T, BITS, P>::is_full_width() {
+            Some(start..SequenceInt::<T, BITS, P>::modulus_mask().wrapping_add(T::ONE))
+        } else {
+            None
+        }
+    }
+}
+
+// Two ranges are equal when they start at the same value and have the same
+// length; empty ranges at different starts are distinct.
+impl<T, const BITS: u8, P> PartialEq for SeqRange<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.len() == other.len()
+    }
+}
+
+impl<T, const BITS: u8, P> Eq for SeqRange<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+}
+
+impl<T, const BITS: u8, P> Hash for SeqRange<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start.hash(state);
+        self.len().hash(state);
+    }
+}
+
+impl<T, const BITS: u8, P> IntoIterator for SeqRange<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Item = SequenceInt<T, BITS, P>;
+    type IntoIter = SeqRangeIter<T, BITS, P>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        SeqRangeIter {
+            next: self.start,
+            remaining: self.len(),
+        }
+    }
+}
+
+/// Collects a run of consecutive sequence numbers, in forward order, into the
+/// range covering them. An empty run gives the empty range at zero.
+///
+/// # Panics
+///
+/// If a value isn't the successor of the one before it, or the run covers the
+/// whole sequence space (which a range can't hold).
+impl<T, const BITS: u8, P> FromIterator<SequenceInt<T, BITS, P>> for SeqRange<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    fn from_iter<I: IntoIterator<Item = SequenceInt<T, BITS, P>>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let Some(start) = iter.next() else {
+            return Self::new(SequenceInt::from(T::ZERO), SequenceInt::from(T::ZERO));
+        };
+        let mut end = start.next();
+        for seq in iter {
+            assert!(
+                seq == end,
+                "non-contiguous sequence numbers: expected {end:?}, got {seq:?}"
+            );
+            end = end.next();
+            assert!(end != start, "run covers the whole sequence space");
+        }
+        Self::new(start, end)
+    }
+}
+
+impl<T, const BITS: u8, P> SequenceInt<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    // `size` capped at the `2^BITS` values in the space, which only a narrow
+    // width's `T` can exceed
+    fn window_len(size: T) -> T {
+        if size.to_u128() > Self::modulus() {
+            Self::modulus_mask().wrapping_add(T::ONE)
+        } else {
+            size
+        }
+    }
+
+    /// Iterates over the `size` sequence numbers from `start` on, across the
+    /// wrap, the same values [`in_window`](Self::in_window) accepts.
+    ///
+    /// A `size` of `2^BITS` or more yields every value in the space once.
+    #[must_use]
+    pub fn window_iter(start: Self, size: T) -> SeqRangeIter<T, BITS, P> {
+        SeqRangeIter {
+            next: start,
+            remaining: Self::window_len(size),
+        }
+    }
+
+    /// The range `[self, end)`, or an error if it would hold more than
+    /// `max_len` values; bounds what a reassembly buffer sized from it can
+    /// allocate.
+    pub fn range_to_checked(
+        self,
+        end: Self,
+        max_len: T,
+    ) -> Result<SeqRange<T, BITS, P>, GapTooLarge> {
+        let len = self.forward_distance(end);
+        if len > max_len {
+            return Err(GapTooLarge {
+                len: len.to_u64(),
+                max_len: max_len.to_u64(),
+            });
+        }
+        Ok(SeqRange::new(self, end))
+    }
+
+    /// Splits the window `[self, self + size)` into its lower and upper
+    /// halves, e.g. for double-buffering.
+    ///
+    /// For an odd `size` the lower half gets `size / 2` values and the upper
+    /// half the one left over. A `size` beyond `2^BITS` is capped there, as in
+    /// [`window_iter`](Self::window_iter), so each half is then half the space.
+    #[must_use]
+    pub fn split_window(self, size: T) -> (SeqRange<T, BITS, P>, SeqRange<T, BITS, P>) {
+        let size = Self::window_len(size);
+        let mid = self + size.shr(1);
+        (SeqRange::new(self, mid), SeqRange::new(mid, self + size))
+    }
+}
+
+/// Iterator over the values of a [`SeqRange`], in forward order, or backward
+/// from the end with [`rev`](Iterator::rev).
+#[derive(Clone, Debug)]
+pub struct SeqRangeIter<T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    next: SequenceInt<T, BITS, P>,
+    remaining: T,
+}
+
+impl<T, const BITS: u8, P> Iterator for SeqRangeIter<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Item = SequenceInt<T, BITS, P>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == T::ZERO {
+            return None;
+        }
+        let seq = self.next;
+        self.next.inc();
+        self.remaining = self.remaining.wrapping_sub(T::ONE);
+        Some(seq)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining.to_u64()) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T, const BITS: u8, P> DoubleEndedIterator for SeqRangeIter<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == T::ZERO {
+            return None;
+        }
+        self.remaining = self.remaining.wrapping_sub(T::ONE);
+        Some(self.next + self.remaining)
+    }
+}
+
+impl<T, const BITS: u8, P> FusedIterator for SeqRangeIter<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+}
+
+// Only where every possible length fits in a usize, same as std does for
+// `Range<u64>`.
+macro_rules! impl_exact_size {
+    ($($t:ty),*) => {
+        $(impl<const BITS: u8, P: TieBreak> ExactSizeIterator for SeqRangeIter<$t, BITS, P> {})*
+    };
+}
+
+impl_exact_size!(u8, u16, u32);
+#[cfg(target_pointer_width = "64")]
+impl_exact_size!(u64);
+
+#[test]
+fn test_as_std_range() {
+    use crate::SeqU16;
+
+    let r = SeqRange::new(SeqU16::from(10), SeqU16::from(20));
+    assert_eq!(r.len(), 10);
+    assert_eq!(r.as_std_range(), Some(10..20));
+    assert!(r.contains(SeqU16::from(10)) && !r.contains(SeqU16::from(20)));
+
+    let empty = SeqRange::new(SeqU16::from(7), SeqU16::from(7));
+    assert!(empty.is_empty());
+    assert_eq!(empty.as_std_range(), Some(7..7));
+
+    let wrapping = SeqRange::new(SeqU16::from(65530), SeqU16::from(4));
+    assert_eq!(wrapping.len(), 10);
+    assert!(wrapping.contains(SeqU16::from(0)));
+    assert_eq!(wrapping.as_std_range(), None);
+
+    // ends at the top of the space
+    assert_eq!(
+        SeqRange::new(SeqU16::from(65530), SeqU16::from(0)).as_std_range(),
+        None
+    );
+    type S14 = SequenceInt<u32, 14>;
+    assert_eq!(
+        SeqRange::new(S14::from(16_380), S14::from(0)).as_std_range(),
+        Some(16_380..16_384)
+    );
+    assert_eq!(
+        SeqRange::new(S14::from(16_380), S14::from(1)).as_std_range(),
+        None
+    );
+}
+
+#[test]
+fn test_range_iter_len() {
+    use crate::SeqU16;
+
+    let mut iter = SeqRange::new(SeqU16::from(65533), SeqU16::from(3)).into_iter();
+    assert_eq!(iter.len(), 6);
+    assert_eq!(iter.size_hint(), (6, Some(6)));
+    iter.next();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.by_ref().count(), 5);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_range_iter_rev() {
+    use crate::SeqU16;
+
+    let r = SeqRange::new(SeqU16::from(65533), SeqU16::from(3));
+    let forward = r.into_iter().map(|s| s.0).collect::<Vec<_>>();
+    let mut backward = r.into_iter().rev().map(|s| s.0).collect::<Vec<_>>();
+    assert_eq!(backward, [2, 1, 0, 65535, 65534, 65533]);
+    backward.reverse();
+    assert_eq!(forward, backward);
+
+    // both ends meet in the middle without repeating a value
+    let mut iter = r.into_iter();
+    let mut seen = Vec::new();
+    while let Some(s) = iter.next() {
+        seen.push(s.0);
+        seen.extend(iter.next_back().map(|s| s.0));
+    }
+    assert_eq!(seen, [65533, 2, 65534, 1, 65535, 0]);
+    assert_eq!(iter.next_back(), None);
+
+    let mut odd = SeqRange::new(SeqU16::from(1), SeqU16::from(4)).into_iter();
+    assert_eq!(odd.next_back(), Some(SeqU16::from(3)));
+    assert_eq!(odd.next(), Some(SeqU16::from(1)));
+    assert_eq!(odd.next_back(), Some(SeqU16::from(2)));
+    assert_eq!((odd.next(), odd.next_back()), (None, None));
+}
+
+#[test]
+fn test_window_iter() {
+    use crate::SeqU16;
+
+    let window = SeqU16::window_iter(SeqU16::from(65533), 5);
+    assert_eq!(window.len(), 5);
+    let values = window.map(|s| s.0).collect::<Vec<_>>();
+    assert_eq!(values, [65533, 65534, 65535, 0, 1]);
+    assert_eq!(SeqU16::window_iter(SeqU16::from(7), 0).next(), None);
+
+    // at or past the size of the space, every value comes once, as
+    // `in_window` accepts them all
+    type S8 = SequenceInt<u32, 8>;
+    for size in [256, 300, u32::MAX] {
+        let values = S8::window_iter(S8::from(250), size).collect::<Vec<_>>();
+        assert_eq!(values.len(), 256);
+        assert_eq!((values[0].get(), values[255].get()), (250, 249));
+        assert!(values.iter().all(|s| s.in_window(S8::from(250), size)));
+    }
+    assert_eq!(S8::window_iter(S8::from(250), 255).count(), 255);
+    assert_eq!(
+        S8::window_iter(S8::from(250), 256).next_back(),
+        Some(S8::from(249))
+    );
+}
+
+#[test]
+fn test_range_eq_hash() {
+    use crate::SeqU16;
+    use std::hash::BuildHasher;
+
+    let hasher = std::hash::RandomState::new();
+    let a = SeqRange::new(SeqU16::from(65534), SeqU16::from(3));
+    let b = SeqU16::from(65534)
+        .range_to_checked(SeqU16::from(3), 5)
+        .unwrap();
+    assert_eq!(a, b);
+    assert_eq!(hasher.hash_one(a), hasher.hash_one(b));
+    assert_ne!(a, SeqRange::new(SeqU16::from(65534), SeqU16::from(4)));
+    assert_ne!(a, SeqRange::new(SeqU16::from(65535), SeqU16::from(3)));
+
+    type S14 = SequenceInt<u32, 14>;
+    let r = SeqRange::new(S14::from(16_380), S14::from(2));
+    assert_eq!(r, S14::window_iter(S14::from(16_380), 6).collect());
+    assert_eq!(
+        hasher.hash_one(r),
+        hasher.hash_one(S14::window_iter(S14::from(16_380), 6).collect::<SeqRange<_, 14>>())
+    );
+    assert_ne!(
+        SeqRange::new(SeqU16::from(5), SeqU16::from(5)),
+        SeqRange::new(SeqU16::from(6), SeqU16::from(6))
+    );
+}
+
+#[test]
+fn test_split_window() {
+    use crate::SeqU16;
+
+    let (lo, hi) = SeqU16::from(65532).split_window(8);
+    assert_eq!(
+        (lo.start(), lo.end()),
+        (SeqU16::from(65532), SeqU16::from(0))
+    );
+    assert_eq!((hi.start(), hi.end()), (SeqU16::from(0), SeqU16::from(4)));
+
+    let (lo, hi) = SeqU16::from(65534).split_window(5);
+    assert_eq!((lo.len(), hi.len()), (2, 3));
+    assert_eq!(
+        lo.into_iter().map(|s| s.0).collect::<Vec<_>>(),
+        [65534, 65535]
+    );
+    assert_eq!(hi.into_iter().map(|s| s.0).collect::<Vec<_>>(), [0, 1, 2]);
+
+    let (lo, hi) = SeqU16::from(9).split_window(1);
+    assert!(lo.is_empty());
+    assert_eq!(hi.len(), 1);
+
+    type S8 = SequenceInt<u32, 8>;
+    for size in [256, 300, u32::MAX] {
+        let (lo, hi) = S8::from(200).split_window(size);
+        assert_eq!((lo.start(), lo.end()), (S8::from(200), S8::from(72)));
+        assert_eq!((hi.start(), hi.end()), (S8::from(72), S8::from(200)));
+        assert_eq!((lo.len(), hi.len()), (128, 128));
+    }
+}
+
+#[test]
+fn test_range_to_checked() {
+    use crate::SeqU16;
+
+    let r = SeqU16::from(65530)
+        .range_to_checked(SeqU16::from(10), 16)
+        .unwrap();
+    assert_eq!((r.start(), r.len()), (SeqU16::from(65530), 16));
+    assert!(
+        SeqU16::from(5)
+            .range_to_checked(SeqU16::from(5), 0)
+            .unwrap()
+            .is_empty()
+    );
+
+    let err = SeqU16::from(65530).range_to_checked(SeqU16::from(11), 16);
+    assert_eq!(
+        err.unwrap_err(),
+        GapTooLarge {
+            len: 17,
+            max_len: 16
+        }
+    );
+    // an end just behind the start is nearly the whole space forward
+    let err = SeqU16::from(100).range_to_checked(SeqU16::from(99), 1500);
+    assert_eq!(err.unwrap_err().len, 65535);
+}
+
+#[test]
+fn test_collect_range() {
+    use crate::SeqU16;
+
+    let r = [65534, 65535, 0, 1]
+        .map(SeqU16::from)
+        .into_iter()
+        .collect::<SeqRange<_, 16>>();
+    assert_eq!((r.start(), r.end()), (SeqU16::from(65534), SeqU16::from(2)));
+    assert!(
+        core::iter::empty::<SeqU16>()
+            .collect::<SeqRange<_, 16>>()
+            .is_empty()
+    );
+
+    let gap = std::panic::catch_unwind(|| {
+        [3, 4, 6]
+            .map(SeqU16::from)
+            .into_iter()
+            .collect::<SeqRange<_, 16>>()
+    });
+    assert!(gap.is_err());
+    let full = std::panic::catch_unwind(|| {
+        (0..=255u8)
+            .map(crate::SeqU8::from)
+            .collect::<SeqRange<_, 8>>()
+    });
+    assert!(full.is_err());
+}
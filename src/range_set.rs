@@ -0,0 +1,395 @@
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::iter::Flatten;
+use core::marker::PhantomData;
+use core::ops::{DerefMut, Range};
+use core::slice;
+
+#[cfg(feature = "smallvec")]
+use smallvec::SmallVec;
+
+use crate::{GreaterWins, SeqRange, SequenceInt, TieBreak, UInt, seal};
+
+/// Backing storage for the ranges of a [`SeqRangeSet`].
+///
+/// Implemented for `Vec` and, with the `smallvec` feature, for `SmallVec`.
+pub trait RangeStorage<T, const BITS: u8, P = GreaterWins>:
+    seal::Sealed + Default + DerefMut<Target = [SeqRange<T, BITS, P>]>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    #[doc(hidden)]
+    fn insert_at(&mut self, index: usize, range: SeqRange<T, BITS, P>);
+    #[doc(hidden)]
+    fn remove_span(&mut self, span: Range<usize>);
+}
+
+impl<T, const BITS: u8, P> seal::Sealed for Vec<SeqRange<T, BITS, P>>
+where
+    T: UInt,
+    P: TieBreak,
+{
+}
+
+impl<T, const BITS: u8, P> RangeStorage<T, BITS, P> for Vec<SeqRange<T, BITS, P>>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    fn insert_at(&mut self, index: usize, range: SeqRange<T, BITS, P>) {
+        self.insert(index, range);
+    }
+
+    fn remove_span(&mut self, span: Range<usize>) {
+        self.drain(span);
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const BITS: u8, const N: usize, P> seal::Sealed for SmallVec<[SeqRange<T, BITS, P>; N]>
+where
+    T: UInt,
+    P: TieBreak,
+{
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const BITS: u8, const N: usize, P> RangeStorage<T, BITS, P>
+    for SmallVec<[SeqRange<T, BITS, P>; N]>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    fn insert_at(&mut self, index: usize, range: SeqRange<T, BITS, P>) {
+        self.insert(index, range);
+    }
+
+    fn remove_span(&mut self, span: Range<usize>) {
+        self.drain(span);
+    }
+}
+
+/// [`SeqRangeSet`] that keeps up to `N` ranges inline before spilling to the
+/// heap.
+#[cfg(feature = "smallvec")]
+pub type SmallSeqRangeSet<T, const BITS: u8, const N: usize, P = GreaterWins> =
+    SeqRangeSet<T, BITS, P, SmallVec<[SeqRange<T, BITS, P>; N]>>;
+
+/// Set of sequence numbers stored as sorted, coalesced [`SeqRange`]s, e.g. to
+/// track which segments of a stream have been received.
+///
+/// Ranges are kept in forward order from the lowest value in the set (its
+/// low-water), so all members are expected to lie within half the sequence
+/// space of each other.
+#[derive(Clone, Debug)]
+pub struct SeqRangeSet<T, const BITS: u8, P = GreaterWins, S = Vec<SeqRange<T, BITS, P>>>
+where
+    T: UInt,
+    P: TieBreak,
+    S: RangeStorage<T, BITS, P>,
+{
+    ranges: S,
+    _storage: PhantomData<SequenceInt<T, BITS, P>>,
+}
+
+impl<T, const BITS: u8, P> SeqRangeSet<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    pub fn new() -> Self {
+        Self {
+            ranges: Vec::new(),
+            _storage: PhantomData,
+        }
+    }
+}
+
+impl<T, const BITS: u8, P, S> Default for SeqRangeSet<T, BITS, P, S>
+where
+    T: UInt,
+    P: TieBreak,
+    S: RangeStorage<T, BITS, P>,
+{
+    fn default() -> Self {
+        Self {
+            ranges: S::default(),
+            _storage: PhantomData,
+        }
+    }
+}
+
+// The coalesced ranges are canonical, so sets holding the same values are
+// equal and hash the same whatever order they were built in
+impl<T, const BITS: u8, P, S> PartialEq for SeqRangeSet<T, BITS, P, S>
+where
+    T: UInt,
+    P: TieBreak,
+    S: RangeStorage<T, BITS, P>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges() == other.ranges()
+    }
+}
+
+impl<T, const BITS: u8, P, S> Eq for SeqRangeSet<T, BITS, P, S>
+where
+    T: UInt,
+    P: TieBreak,
+    S: RangeStorage<T, BITS, P>,
+{
+}
+
+impl<T, const BITS: u8, P, S> Hash for SeqRangeSet<T, BITS, P, S>
+where
+    T: UInt,
+    P: TieBreak,
+    S: RangeStorage<T, BITS, P>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ranges().hash(state);
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const BITS: u8, const N: usize, P> SmallSeqRangeSet<T, BITS, N, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    /// Whether the ranges have outgrown the inline capacity and moved to the
+    /// heap.
+    #[must_use]
+    pub fn spilled(&self) -> bool {
+        self.ranges.spilled()
+    }
+}
+
+impl<T, const BITS: u8, P, S> SeqRangeSet<T, BITS, P, S>
+where
+    T: UInt,
+    P: TieBreak,
+    S: RangeStorage<T, BITS, P>,
+{
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The coalesced ranges, in forward order from the low-water.
+    #[must_use]
+    pub fn ranges(&self) -> &[SeqRange<T, BITS, P>] {
+        &self.ranges
+    }
+
+    /// Lowest sequence number in the set.
+    #[must_use]
+    pub fn low_water(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.ranges.first().map(SeqRange::start)
+    }
+
+    #[must_use]
+    pub fn contains(&self, seq: SequenceInt<T, BITS, P>) -> bool {
+        self.ranges.iter().any(|r| r.contains(seq))
+    }
+
+    /// Adds `seq`, returning `false` if it was already present.
+    pub fn insert(&mut self, seq: SequenceInt<T, BITS, P>) -> bool {
+        if self.contains(seq) {
+            return false;
+        }
+        self.insert_range(SeqRange::new(seq, seq.next()));
+        true
+    }
+
+    /// Adds every value of `range`, merging it with overlapping or adjacent
+    /// ranges already in the set.
+    pub fn insert_range(&mut self, range: SeqRange<T, BITS, P>) {
+        if range.is_empty() {
+            return;
+        }
+        let origin = match self.low_water() {
+            Some(low) if low < range.start() => low,
+            _ => range.start(),
+        };
+        // positions relative to the origin, wide enough for an end at 2^64
+        let key = |seq: SequenceInt<T, BITS, P>| origin.forward_distance(seq).to_u64() as u128;
+        let end_key = |r: &SeqRange<T, BITS, P>| key(r.start()) + r.len().to_u64() as u128;
+
+        let (mut start, mut end) = (range.start(), range.end());
+        let (mut lo, mut hi) = (key(start), end_key(&range));
+        let first = self.ranges.partition_point(|r| end_key(r) < lo);
+        let mut last = first;
+        while let Some(r) = self.ranges.get(last) {
+            if key(r.start()) > hi {
+                break;
+            }
+            if key(r.start()) < lo {
+                lo = key(r.start());
+                start = r.start();
+            }
+            if end_key(r) > hi {
+                hi = end_key(r);
+                end = r.end();
+            }
+            last += 1;
+        }
+        let merged = SeqRange::new(start, end);
+        if first == last {
+            self.ranges.insert_at(first, merged);
+        } else {
+            self.ranges[first] = merged;
+            self.ranges.remove_span(first + 1..last);
+        }
+    }
+
+    /// Iterates over every sequence number in the set, in forward order from
+    /// the low-water.
+    pub fn iter(&self) -> SeqRangeSetIter<'_, T, BITS, P> {
+        self.into_iter()
+    }
+}
+
+impl<'a, T, const BITS: u8, P, S> IntoIterator for &'a SeqRangeSet<T, BITS, P, S>
+where
+    T: UInt,
+    P: TieBreak,
+    S: RangeStorage<T, BITS, P>,
+{
+    type Item = SequenceInt<T, BITS, P>;
+    type IntoIter = SeqRangeSetIter<'a, T, BITS, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SeqRangeSetIter {
+            inner: self.ranges.iter().copied().flatten(),
+        }
+    }
+}
+
+/// Iterator over the individual sequence numbers of a [`SeqRangeSet`].
+#[derive(Clone, Debug)]
+pub struct SeqRangeSetIter<'a, T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    inner: Flatten<core::iter::Copied<slice::Iter<'a, SeqRange<T, BITS, P>>>>,
+}
+
+impl<T, const BITS: u8, P> Iterator for SeqRangeSetIter<'_, T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Item = SequenceInt<T, BITS, P>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[test]
+fn test_range_set_insert() {
+    use crate::SeqU16;
+
+    let mut set = SeqRangeSet::<u16, 16>::new();
+    assert!(set.insert(SeqU16::from(10)));
+    assert!(!set.insert(SeqU16::from(10)));
+    assert!(set.insert(SeqU16::from(12)));
+    assert_eq!(set.ranges().len(), 2);
+    assert!(set.insert(SeqU16::from(11)));
+    assert_eq!(set.ranges().len(), 1);
+    assert_eq!(set.ranges()[0].len(), 3);
+
+    // below the low-water, across the wrap
+    set.insert_range(SeqRange::new(SeqU16::from(65530), SeqU16::from(2)));
+    assert_eq!(set.low_water(), Some(SeqU16::from(65530)));
+    assert_eq!(set.ranges().len(), 2);
+    set.insert_range(SeqRange::new(SeqU16::from(1), SeqU16::from(11)));
+    assert_eq!(set.ranges().len(), 1);
+    assert_eq!(set.ranges()[0].start(), SeqU16::from(65530));
+    assert_eq!(set.ranges()[0].end(), SeqU16::from(13));
+    assert!(set.contains(SeqU16::from(0)) && !set.contains(SeqU16::from(13)));
+}
+
+#[test]
+fn test_range_set_iter() {
+    use crate::SeqU16;
+
+    let mut set = SeqRangeSet::<u16, 16>::new();
+    set.insert_range(SeqRange::new(SeqU16::from(5), SeqU16::from(8)));
+    set.insert_range(SeqRange::new(SeqU16::from(65533), SeqU16::from(1)));
+
+    let values = set.iter().map(|s| s.0).collect::<Vec<_>>();
+    assert_eq!(values, [65533, 65534, 65535, 0, 5, 6, 7]);
+
+    let mut n = 0;
+    for seq in &set {
+        assert!(set.contains(seq));
+        n += 1;
+    }
+    assert_eq!(n, 7);
+    assert_eq!(SeqRangeSet::<u16, 16>::new().iter().next(), None);
+}
+
+#[test]
+fn test_range_set_eq_hash() {
+    use crate::SeqU16;
+    use std::hash::BuildHasher;
+
+    let hasher = std::hash::RandomState::new();
+    let mut values = [65530u16, 65531, 65535, 0, 1, 2, 7, 9, 10, 11, 30].map(SeqU16::from);
+    let mut forward = SeqRangeSet::<u16, 16>::new();
+    values.iter().for_each(|&v| {
+        forward.insert(v);
+    });
+
+    let mut state = 0xfeed_u64;
+    for _ in 0..50 {
+        // Fisher-Yates shuffle of the insertion order
+        for i in (1..values.len()).rev() {
+            values.swap(i, crate::next_rand(&mut state) as usize % (i + 1));
+        }
+        let mut shuffled = SeqRangeSet::<u16, 16>::new();
+        for &v in &values {
+            shuffled.insert(v);
+        }
+        assert_eq!(shuffled, forward);
+        assert_eq!(hasher.hash_one(&shuffled), hasher.hash_one(&forward));
+    }
+
+    let mut other = forward.clone();
+    other.insert(SeqU16::from(31));
+    assert_ne!(other, forward);
+    assert_eq!(SeqRangeSet::<u16, 16>::new(), SeqRangeSet::default());
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_small_range_set() {
+    use crate::SeqU16;
+
+    let mut state = 0x5eed_u64;
+    let mut small = SmallSeqRangeSet::<u16, 16, 4>::default();
+    let mut vec = SeqRangeSet::<u16, 16>::new();
+    for _ in 0..500 {
+        let start = SeqU16::from(crate::next_rand(&mut state) as u16 % 2000);
+        let len = (crate::next_rand(&mut state) % 8) as u16;
+        let range = SeqRange::new(start, start + len);
+        small.insert_range(range);
+        vec.insert_range(range);
+        assert_eq!(small.ranges(), vec.ranges());
+    }
+    assert!(small.iter().eq(vec.iter()));
+
+    let mut set = SmallSeqRangeSet::<u16, 16, 2>::default();
+    set.insert_range(SeqRange::new(SeqU16::from(65530), SeqU16::from(2)));
+    set.insert_range(SeqRange::new(SeqU16::from(10), SeqU16::from(20)));
+    set.insert(SeqU16::from(2));
+    assert_eq!(set.ranges().len(), 2);
+    assert!(!set.spilled());
+    set.insert(SeqU16::from(30));
+    assert!(set.spilled());
+}
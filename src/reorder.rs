@@ -0,0 +1,195 @@
+use core::fmt;
+
+use crate::{GreaterWins, SequenceInt, TieBreak, UInt};
+
+/// Why [`FixedReorderBuffer::push`] refused a value, which it hands back.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PushError<V> {
+    /// No slot is free for the value: all `N` hold values still waiting for
+    /// an earlier one, or the only one left is kept for the next expected.
+    Full(V),
+    /// The sequence number is behind the next one expected, so was already
+    /// delivered or given up on.
+    Stale(V),
+}
+
+impl<V> PushError<V> {
+    /// The value that was refused.
+    pub fn into_inner(self) -> V {
+        match self {
+            Self::Full(v) | Self::Stale(v) => v,
+        }
+    }
+}
+
+impl<V> fmt::Display for PushError<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("reorder buffer is full"),
+            Self::Stale(_) => f.write_str("sequence number is behind the reorder buffer"),
+        }
+    }
+}
+
+impl<V: fmt::Debug> core::error::Error for PushError<V> {}
+
+/// Puts values arriving out of order back in sequence, holding at most `N`
+/// of them in a fixed array, so it never allocates.
+///
+/// Values are [`push`](Self::push)ed as they arrive and come out of
+/// [`pop_ready`](Self::pop_ready) in sequence order, once every earlier one
+/// has.
+#[derive(Clone, Debug)]
+pub struct FixedReorderBuffer<T, const BITS: u8, V, const N: usize, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    next: SequenceInt<T, BITS, P>,
+    slots: [Option<(SequenceInt<T, BITS, P>, V)>; N],
+    len: usize,
+}
+
+impl<T, const BITS: u8, V, const N: usize, P> FixedReorderBuffer<T, BITS, V, N, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    /// Creates an empty buffer that delivers from `next` on.
+    pub fn new(next: SequenceInt<T, BITS, P>) -> Self {
+        Self {
+            next,
+            slots: core::array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+
+    /// The sequence number [`pop_ready`](Self::pop_ready) is waiting for.
+    #[must_use]
+    pub fn next_expected(&self) -> SequenceInt<T, BITS, P> {
+        self.next
+    }
+
+    /// Number of values held.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Stores `value` for `seq`, replacing any value already held for it.
+    ///
+    /// The last free slot is kept for the next expected sequence number, so
+    /// a buffer filled with later values can always take the one that lets
+    /// them drain rather than stalling with every slot taken.
+    pub fn push(&mut self, seq: SequenceInt<T, BITS, P>, value: V) -> Result<(), PushError<V>> {
+        if seq < self.next {
+            return Err(PushError::Stale(value));
+        }
+        if let Some(slot) = self.slots.iter_mut().flatten().find(|(s, _)| *s == seq) {
+            slot.1 = value;
+            return Ok(());
+        }
+        if seq != self.next && self.len + 1 == N && !self.holds(self.next) {
+            return Err(PushError::Full(value));
+        }
+        match self.slots.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some((seq, value));
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(PushError::Full(value)),
+        }
+    }
+
+    fn holds(&self, seq: SequenceInt<T, BITS, P>) -> bool {
+        self.slots.iter().flatten().any(|(s, _)| *s == seq)
+    }
+
+    /// Takes the value for the next expected sequence number, if it has
+    /// arrived, and moves on to the one after.
+    pub fn pop_ready(&mut self) -> Option<(SequenceInt<T, BITS, P>, V)> {
+        let next = self.next;
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|slot| matches!(slot, Some((s, _)) if *s == next))?;
+        let ready = slot.take();
+        self.len -= 1;
+        self.next.inc();
+        ready
+    }
+}
+
+#[test]
+fn test_fixed_reorder_buffer() {
+    use crate::SeqU16;
+
+    let mut buf = FixedReorderBuffer::<u16, 16, &str, 3>::new(SeqU16::from(65534));
+    assert_eq!(buf.push(SeqU16::from(0), "c"), Ok(()));
+    assert_eq!(buf.pop_ready(), None);
+    assert_eq!(buf.push(SeqU16::from(65534), "a"), Ok(()));
+    assert_eq!(buf.push(SeqU16::from(65535), "b"), Ok(()));
+    assert!(buf.is_full());
+    assert_eq!(buf.push(SeqU16::from(2), "e"), Err(PushError::Full("e")));
+    assert_eq!(buf.push(SeqU16::from(0), "C"), Ok(()));
+
+    assert_eq!(buf.pop_ready(), Some((SeqU16::from(65534), "a")));
+    assert_eq!(buf.pop_ready(), Some((SeqU16::from(65535), "b")));
+    assert_eq!(buf.pop_ready(), Some((SeqU16::from(0), "C")));
+    assert_eq!(buf.pop_ready(), None);
+    assert!(buf.is_empty());
+    assert_eq!(buf.next_expected(), SeqU16::from(1));
+
+    buf.push(SeqU16::from(2), "e").unwrap();
+    assert_eq!(
+        buf.push(SeqU16::from(65535), "late"),
+        Err(PushError::Stale("late"))
+    );
+    buf.push(SeqU16::from(1), "d").unwrap();
+    assert_eq!(buf.pop_ready(), Some((SeqU16::from(1), "d")));
+    assert_eq!(buf.pop_ready(), Some((SeqU16::from(2), "e")));
+    assert_eq!(buf.len(), 0);
+    assert_eq!(PushError::Full(7).into_inner(), 7);
+}
+
+#[test]
+fn test_fixed_reorder_buffer_reserves_next() {
+    use crate::SeqU8;
+
+    let mut buf = FixedReorderBuffer::<u8, 8, u8, 4>::new(SeqU8::from(10));
+    for s in 11..14 {
+        assert_eq!(buf.push(SeqU8::from(s), s), Ok(()));
+    }
+    // the last slot stays free for 10, so the buffer can't wedge
+    assert_eq!(buf.push(SeqU8::from(14), 14), Err(PushError::Full(14)));
+    assert_eq!(buf.push(SeqU8::from(12), 12), Ok(()));
+    assert_eq!(buf.push(SeqU8::from(10), 10), Ok(()));
+    assert!(buf.is_full());
+    for s in 10..14 {
+        assert_eq!(buf.pop_ready(), Some((SeqU8::from(s), s)));
+    }
+    assert!(buf.is_empty());
+
+    // with `next` already held, every slot is usable
+    buf.push(SeqU8::from(14), 14).unwrap();
+    for s in 15..18 {
+        assert_eq!(buf.push(SeqU8::from(s), s), Ok(()));
+    }
+    assert!(buf.is_full());
+
+    // a single slot only ever takes `next`
+    let mut one = FixedReorderBuffer::<u8, 8, u8, 1>::new(SeqU8::from(0));
+    assert_eq!(one.push(SeqU8::from(1), 1), Err(PushError::Full(1)));
+    assert_eq!(one.push(SeqU8::from(0), 0), Ok(()));
+}
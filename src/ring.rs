@@ -0,0 +1,156 @@
+use alloc::vec::Vec;
+use core::ops::Index;
+
+use crate::{GreaterWins, SequenceInt, TieBreak, UInt};
+
+/// Ring buffer holding a value for each of the last `size` sequence numbers up
+/// to the high-water, e.g. packets awaiting reassembly.
+///
+/// `seq` lives in slot `seq mod size`; since `size` is a power of two no larger
+/// than half the sequence space, that stays consistent across the wrap.
+/// Inserting a newer sequence number moves the window forward and evicts
+/// whatever falls out of it.
+#[derive(Clone, Debug)]
+pub struct SeqRing<T, const BITS: u8, V, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    high: Option<SequenceInt<T, BITS, P>>,
+    slots: Vec<Option<V>>,
+}
+
+impl<T, const BITS: u8, V, P> SeqRing<T, BITS, V, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    /// Creates an empty ring with room for `size` sequence numbers.
+    ///
+    /// # Panics
+    ///
+    /// If `size` isn't a power of two, or is larger than half the sequence
+    /// space.
+    pub fn new(size: usize) -> Self {
+        assert!(
+            size.is_power_of_two() && size as u64 <= 1 << (BITS - 1),
+            "ring size must be a power of two up to 2^{}, got {size}",
+            BITS - 1
+        );
+        let mut slots = Vec::with_capacity(size);
+        slots.resize_with(size, || None);
+        Self { high: None, slots }
+    }
+
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Highest sequence number inserted so far, if any.
+    #[must_use]
+    pub fn high_water(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.high
+    }
+
+    fn slot(&self, seq: SequenceInt<T, BITS, P>) -> usize {
+        seq.0.to_u64() as usize & (self.slots.len() - 1)
+    }
+
+    /// Whether `seq` is at or behind the high-water by less than `size`.
+    fn in_window(&self, seq: SequenceInt<T, BITS, P>) -> bool {
+        self.high
+            .is_some_and(|high| seq.forward_distance(high).to_u64() < self.slots.len() as u64)
+    }
+
+    /// The value stored for `seq`, if `seq` is within the window and has one.
+    #[must_use]
+    pub fn get(&self, seq: SequenceInt<T, BITS, P>) -> Option<&V> {
+        if !self.in_window(seq) {
+            return None;
+        }
+        self.slots[self.slot(seq)].as_ref()
+    }
+
+    /// Stores `value` for `seq`, replacing any value it already had.
+    ///
+    /// A `seq` ahead of the high-water becomes the new high-water, evicting
+    /// the entries that drop out of the window. Returns `false`, dropping
+    /// `value`, if `seq` is already behind the window.
+    pub fn insert(&mut self, seq: SequenceInt<T, BITS, P>, value: V) -> bool {
+        match self.high {
+            Some(high) if high.distance(seq) <= 0 => {
+                if !self.in_window(seq) {
+                    return false;
+                }
+            }
+            Some(high) => {
+                let ahead = high.forward_distance(seq).to_u64();
+                let evict = ahead.min(self.slots.len() as u64);
+                let mut stale = high;
+                for _ in 0..evict {
+                    stale.inc();
+                    let slot = self.slot(stale);
+                    self.slots[slot] = None;
+                }
+                self.high = Some(seq);
+            }
+            None => self.high = Some(seq),
+        }
+        let slot = self.slot(seq);
+        self.slots[slot] = Some(value);
+        true
+    }
+}
+
+impl<T, const BITS: u8, V, P> Index<SequenceInt<T, BITS, P>> for SeqRing<T, BITS, V, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    type Output = V;
+
+    /// # Panics
+    ///
+    /// If there is no value for `seq`; see [`SeqRing::get`].
+    fn index(&self, seq: SequenceInt<T, BITS, P>) -> &V {
+        self.get(seq).expect("no value for sequence number")
+    }
+}
+
+#[test]
+fn test_seq_ring() {
+    use crate::SeqU16;
+
+    let mut ring = SeqRing::<u16, 16, &str>::new(4);
+    assert_eq!(ring.get(SeqU16::from(0)), None);
+    assert!(ring.insert(SeqU16::from(65534), "a"));
+    assert!(ring.insert(SeqU16::from(0), "c"));
+    assert_eq!(ring.get(SeqU16::from(65534)), Some(&"a"));
+    assert_eq!(ring.get(SeqU16::from(65535)), None);
+    assert_eq!(ring[SeqU16::from(0)], "c");
+    assert!(ring.insert(SeqU16::from(65535), "b"));
+    assert_eq!(ring.get(SeqU16::from(65535)), Some(&"b"));
+    assert_eq!(ring.high_water(), Some(SeqU16::from(0)));
+
+    // moving to 2 evicts 65534
+    assert!(ring.insert(SeqU16::from(2), "e"));
+    assert_eq!(ring.get(SeqU16::from(65534)), None);
+    assert_eq!(ring.get(SeqU16::from(65535)), Some(&"b"));
+    assert_eq!(ring.get(SeqU16::from(1)), None);
+    assert!(!ring.insert(SeqU16::from(65534), "late"));
+
+    // a jump past the whole window clears it, including a slot that the new
+    // window reuses
+    assert!(ring.insert(SeqU16::from(7), "x"));
+    assert_eq!(ring.get(SeqU16::from(2)), None);
+    assert_eq!(ring.get(SeqU16::from(6)), None);
+    assert_eq!(ring.get(SeqU16::from(7)), Some(&"x"));
+    assert_eq!(ring.get(SeqU16::from(8)), None);
+}
+
+#[test]
+#[should_panic(expected = "power of two")]
+fn test_seq_ring_size() {
+    let _ = SeqRing::<u32, 14, ()>::new(12);
+}
@@ -0,0 +1,277 @@
+//! Serde support.
+//!
+//! By default a [`SequenceInt`] (de)serializes as its bare inner integer, and
+//! deserialization masks the value into the sequence space like `From<T>`.
+//! The submodules offer alternative representations for `#[serde(with)]`.
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{SequenceInt, TieBreak, UInt};
+
+impl<T, const BITS: u8, P> Serialize for SequenceInt<T, BITS, P>
+where
+    T: UInt + Serialize,
+    P: TieBreak,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T, const BITS: u8, P> Deserialize<'de> for SequenceInt<T, BITS, P>
+where
+    T: UInt + Deserialize<'de>,
+    P: TieBreak,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Self-describing form, `{ "bits": 24, "value": 10 }`, for use with
+/// `#[serde(with = "seqnum::serde::tagged")]`.
+///
+/// Deserializing fails if the encoded `bits` differs from the target width.
+pub mod tagged {
+    use ::serde::de::Error;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{SequenceInt, TieBreak, UInt};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(crate = "::serde")]
+    struct Tagged<T> {
+        bits: u8,
+        value: T,
+    }
+
+    pub fn serialize<T, const BITS: u8, P, S>(
+        seq: &SequenceInt<T, BITS, P>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: UInt + Serialize,
+        P: TieBreak,
+        S: Serializer,
+    {
+        Tagged {
+            bits: BITS,
+            value: seq.0,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, const BITS: u8, P, D>(
+        deserializer: D,
+    ) -> Result<SequenceInt<T, BITS, P>, D::Error>
+    where
+        T: UInt + Deserialize<'de>,
+        P: TieBreak,
+        D: Deserializer<'de>,
+    {
+        let tagged = Tagged::<T>::deserialize(deserializer)?;
+        if tagged.bits != BITS {
+            return Err(D::Error::custom(format_args!(
+                "expected a {BITS}-bit sequence number, got {} bits",
+                tagged.bits
+            )));
+        }
+        Ok(SequenceInt::from(tagged.value))
+    }
+}
+
+/// Like the default form, but deserializing fails on values that don't fit in
+/// `BITS` bits instead of masking them, to catch corrupt data. Use with
+/// `#[serde(with = "seqnum::serde::strict")]`.
+pub mod strict {
+    use ::serde::de::Error;
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{SequenceInt, TieBreak, UInt};
+
+    pub fn serialize<T, const BITS: u8, P, S>(
+        seq: &SequenceInt<T, BITS, P>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: UInt + Serialize,
+        P: TieBreak,
+        S: Serializer,
+    {
+        seq.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, const BITS: u8, P, D>(
+        deserializer: D,
+    ) -> Result<SequenceInt<T, BITS, P>, D::Error>
+    where
+        T: UInt + Deserialize<'de>,
+        P: TieBreak,
+        D: Deserializer<'de>,
+    {
+        SequenceInt::new(T::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Like the default form, but deserializing also accepts the number as a
+/// decimal string, e.g. `"10"` as well as `10`, for inconsistent encoders.
+/// Serializes as a bare integer. Use with
+/// `#[serde(with = "seqnum::serde::lenient")]`.
+pub mod lenient {
+    use core::fmt;
+    use core::marker::PhantomData;
+
+    use ::serde::de::{Error, Unexpected, Visitor};
+    use ::serde::{Deserializer, Serialize, Serializer};
+
+    use crate::{SequenceInt, TieBreak, UInt};
+
+    pub fn serialize<T, const BITS: u8, P, S>(
+        seq: &SequenceInt<T, BITS, P>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: UInt + Serialize,
+        P: TieBreak,
+        S: Serializer,
+    {
+        seq.serialize(serializer)
+    }
+
+    struct NumberOrString<T>(PhantomData<T>);
+
+    impl<T: UInt> Visitor<'_> for NumberOrString<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a u{} as a number or a string", T::BITS)
+        }
+
+        fn visit_u64<E: Error>(self, v: u64) -> Result<T, E> {
+            let value = T::truncate_from_u64(v);
+            if value.to_u64() != v {
+                return Err(E::invalid_value(Unexpected::Unsigned(v), &self));
+            }
+            Ok(value)
+        }
+
+        fn visit_i64<E: Error>(self, v: i64) -> Result<T, E> {
+            match u64::try_from(v) {
+                Ok(v) => self.visit_u64(v),
+                Err(_) => Err(E::invalid_value(Unexpected::Signed(v), &self)),
+            }
+        }
+
+        fn visit_str<E: Error>(self, v: &str) -> Result<T, E> {
+            match v.parse::<u64>() {
+                Ok(n) => self.visit_u64(n),
+                Err(_) => Err(E::invalid_value(Unexpected::Str(v), &self)),
+            }
+        }
+    }
+
+    pub fn deserialize<'de, T, const BITS: u8, P, D>(
+        deserializer: D,
+    ) -> Result<SequenceInt<T, BITS, P>, D::Error>
+    where
+        T: UInt,
+        P: TieBreak,
+        D: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(NumberOrString::<T>(PhantomData))
+            .map(SequenceInt::from)
+    }
+}
+
+#[test]
+fn test_serde_untagged() {
+    use crate::{SeqU16, SeqU24};
+
+    assert_eq!(serde_json::to_string(&SeqU16::from(10)).unwrap(), "10");
+    assert_eq!(
+        serde_json::from_str::<SeqU16>("65535").unwrap(),
+        SeqU16::from(65535)
+    );
+    assert_eq!(
+        serde_json::from_str::<SeqU24>("16777226").unwrap(),
+        SeqU24::from(10)
+    );
+    assert!(serde_json::from_str::<SeqU16>("65536").is_err());
+}
+
+#[test]
+fn test_serde_tagged() {
+    use crate::SeqU24;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(crate = "::serde")]
+    struct Packet {
+        #[serde(with = "tagged")]
+        seq: SeqU24,
+    }
+
+    let p = Packet {
+        seq: SeqU24::from(10),
+    };
+    let json = serde_json::to_string(&p).unwrap();
+    assert_eq!(json, r#"{"seq":{"bits":24,"value":10}}"#);
+    assert_eq!(serde_json::from_str::<Packet>(&json).unwrap(), p);
+
+    let err = serde_json::from_str::<Packet>(r#"{"seq":{"bits":16,"value":10}}"#).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected a 24-bit sequence number, got 16 bits")
+    );
+}
+
+#[test]
+fn test_serde_strict() {
+    use crate::SeqU24;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(crate = "::serde")]
+    struct Packet {
+        #[serde(with = "strict")]
+        seq: SeqU24,
+    }
+
+    let p = serde_json::from_str::<Packet>(r#"{"seq":16777215}"#).unwrap();
+    assert_eq!(p.seq, SeqU24::from(16_777_215));
+    assert_eq!(serde_json::to_string(&p).unwrap(), r#"{"seq":16777215}"#);
+
+    let err = serde_json::from_str::<Packet>(r#"{"seq":16777226}"#).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("16777226 is out of range for 24 bits")
+    );
+}
+
+#[test]
+fn test_serde_lenient() {
+    use crate::SeqU24;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(crate = "::serde")]
+    struct Packet {
+        #[serde(with = "lenient")]
+        seq: SeqU24,
+    }
+
+    let bare = serde_json::from_str::<Packet>(r#"{"seq":10}"#).unwrap();
+    let quoted = serde_json::from_str::<Packet>(r#"{"seq":"10"}"#).unwrap();
+    assert_eq!(bare, quoted);
+    assert_eq!(bare.seq, SeqU24::from(10));
+    assert_eq!(serde_json::to_string(&quoted).unwrap(), r#"{"seq":10}"#);
+
+    // masked like the default form
+    let p = serde_json::from_str::<Packet>(r#"{"seq":"16777226"}"#).unwrap();
+    assert_eq!(p.seq, SeqU24::from(10));
+    for bad in [
+        r#"{"seq":"ten"}"#,
+        r#"{"seq":-1}"#,
+        r#"{"seq":"4294967296"}"#,
+    ] {
+        assert!(serde_json::from_str::<Packet>(bad).is_err(), "{bad}");
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{GreaterWins, SequenceInt, TieBreak, UInt};
+
+/// Tracks the extent of a stream of sequence numbers relative to the first
+/// one seen, for telemetry.
+///
+/// Each observation is placed by its signed distance from the newest one so
+/// far, so the stream may wrap any number of times as long as no observation
+/// is half the sequence space or more away from the newest.
+#[derive(Clone, Debug, Default)]
+pub struct SeqStats<T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    first: Option<SequenceInt<T, BITS, P>>,
+    // offsets from `first`
+    min: i64,
+    max: i64,
+}
+
+impl<T, const BITS: u8, P> SeqStats<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    pub fn new() -> Self {
+        Self {
+            first: None,
+            min: 0,
+            max: 0,
+        }
+    }
+
+    /// Records `seq`, extending the newest or oldest extreme if it lies
+    /// beyond it.
+    pub fn observe(&mut self, seq: SequenceInt<T, BITS, P>) {
+        let Some(first) = self.first else {
+            self.first = Some(seq);
+            return;
+        };
+        let newest = Self::at(first, self.max);
+        let offset = self.max.saturating_add(newest.distance(seq));
+        self.min = self.min.min(offset);
+        self.max = self.max.max(offset);
+    }
+
+    fn at(first: SequenceInt<T, BITS, P>, offset: i64) -> SequenceInt<T, BITS, P> {
+        first + T::truncate_from_u64(offset as u64)
+    }
+
+    /// The first sequence number observed, if any.
+    #[must_use]
+    pub fn first(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.first
+    }
+
+    /// The furthest-ahead sequence number observed, if any.
+    #[must_use]
+    pub fn newest(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.first.map(|first| Self::at(first, self.max))
+    }
+
+    /// The furthest-behind sequence number observed, if any.
+    #[must_use]
+    pub fn oldest(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.first.map(|first| Self::at(first, self.min))
+    }
+
+    /// Steps from [`oldest`](Self::oldest) to [`newest`](Self::newest), wraps
+    /// included, so the stream covered `span() + 1` values. Zero until there
+    /// are two distinct observations.
+    #[must_use]
+    pub fn span(&self) -> u64 {
+        self.max.abs_diff(self.min)
+    }
+
+    /// How far [`newest`](Self::newest) is ahead of [`first`](Self::first).
+    #[must_use]
+    pub fn ahead_of_first(&self) -> u64 {
+        self.max as u64
+    }
+
+    /// How far [`oldest`](Self::oldest) is behind [`first`](Self::first),
+    /// e.g. from values that were already late when the stream was joined.
+    #[must_use]
+    pub fn behind_first(&self) -> u64 {
+        self.min.unsigned_abs()
+    }
+}
+
+#[test]
+fn test_seq_stats() {
+    use crate::SeqU16;
+
+    let mut stats = SeqStats::<u16, 16>::new();
+    assert_eq!(
+        (stats.newest(), stats.oldest(), stats.span()),
+        (None, None, 0)
+    );
+    stats.observe(SeqU16::from(65530));
+    assert_eq!(stats.newest(), Some(SeqU16::from(65530)));
+    assert_eq!(stats.span(), 0);
+
+    for v in [65533, 65535, 2, 1, 65528, 7, 4] {
+        stats.observe(SeqU16::from(v));
+    }
+    assert_eq!(stats.first(), Some(SeqU16::from(65530)));
+    assert_eq!(stats.newest(), Some(SeqU16::from(7)));
+    assert_eq!(stats.oldest(), Some(SeqU16::from(65528)));
+    assert_eq!((stats.ahead_of_first(), stats.behind_first()), (13, 2));
+    assert_eq!(stats.span(), 15);
+
+    // an 8-bit stream wrapping several times
+    let mut stats = SeqStats::<u8, 8>::new();
+    let mut v = 0u64;
+    for step in [100u64, 100, 100, 100, 100] {
+        stats.observe(SequenceInt::from(v as u8));
+        v += step;
+    }
+    stats.observe(SequenceInt::from(v as u8));
+    assert_eq!(stats.span(), 500);
+    assert_eq!(stats.newest(), Some(SequenceInt::from((500 % 256) as u8)));
+    assert_eq!(stats.oldest(), Some(SequenceInt::from(0)));
+}
@@ -0,0 +1,70 @@
+//! Assertions for downstream test suites, behind the `testing` feature.
+
+use crate::{SequenceInt, TieBreak, UInt};
+
+/// Panics unless `v` survives encoding to and decoding from its
+/// `N = ceil(BITS / 8)` big-, little- and native-endian bytes.
+/// `N` comes first so the rest can be inferred, e.g.
+/// `assert_roundtrip::<3, _, 24, _>(SeqU24::from(0x12_3456))`.
+#[track_caller]
+pub fn assert_roundtrip<const N: usize, T, const BITS: u8, P>(v: SequenceInt<T, BITS, P>)
+where
+    T: UInt,
+    P: TieBreak,
+{
+    let be = v.to_be_bytes::<N>();
+    let back = SequenceInt::from_be_bytes(be);
+    assert!(
+        back == v,
+        "{v:?} did not round-trip through big-endian bytes {be:02x?}: decoded {back:?}"
+    );
+    let le = v.to_le_bytes::<N>();
+    let back = SequenceInt::from_le_bytes(le);
+    assert!(
+        back == v,
+        "{v:?} did not round-trip through little-endian bytes {le:02x?}: decoded {back:?}"
+    );
+    let ne = v.to_ne_bytes::<N>();
+    let back = SequenceInt::from_ne_bytes(ne);
+    assert!(
+        back == v,
+        "{v:?} did not round-trip through native-endian bytes {ne:02x?}: decoded {back:?}"
+    );
+}
+
+/// Panics unless `v` survives serializing to and deserializing from JSON.
+#[cfg(feature = "serde")]
+#[track_caller]
+pub fn assert_serde_roundtrip<T, const BITS: u8, P>(v: SequenceInt<T, BITS, P>)
+where
+    T: UInt + ::serde::Serialize + ::serde::de::DeserializeOwned,
+    P: TieBreak,
+{
+    let json =
+        serde_json::to_string(&v).unwrap_or_else(|e| panic!("{v:?} failed to serialize: {e}"));
+    let back = serde_json::from_str::<SequenceInt<T, BITS, P>>(&json)
+        .unwrap_or_else(|e| panic!("{v:?} failed to deserialize from {json}: {e}"));
+    assert!(
+        back == v,
+        "{v:?} did not round-trip through serde: {json} decoded {back:?}"
+    );
+}
+
+#[test]
+fn test_assert_roundtrip() {
+    use crate::{LessWins, SeqU8, SeqU16, SeqU24, SeqU32, SeqU64};
+
+    assert_roundtrip::<1, _, 8, _>(SeqU8::from(0xab));
+    assert_roundtrip::<2, _, 16, _>(SeqU16::from(0xfffe));
+    assert_roundtrip::<3, _, 24, _>(SeqU24::from(0xff_ffff));
+    assert_roundtrip::<4, _, 32, _>(SeqU32::from(0xdead_beef));
+    assert_roundtrip::<8, _, 64, _>(SeqU64::from(u64::MAX - 1));
+    assert_roundtrip::<2, _, 14, _>(SequenceInt::<u32, 14>::from(0x3abc));
+    assert_roundtrip::<1, _, 1, _>(SequenceInt::<u64, 1, LessWins>::from(1));
+
+    #[cfg(feature = "serde")]
+    {
+        assert_serde_roundtrip(SeqU24::from(0x12_3456));
+        assert_serde_roundtrip(SeqU64::from(u64::MAX));
+    }
+}
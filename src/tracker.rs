@@ -0,0 +1,150 @@
+use crate::{GreaterWins, SequenceInt, TieBreak, UInt};
+
+/// How an arriving sequence number relates to what a [`SeqTracker`] has seen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Arrival {
+    /// Newer than anything seen so far (possibly after a gap).
+    InOrder,
+    /// Older than the high-water; either late or a duplicate of an older one.
+    Reorder,
+    /// The high-water itself, again.
+    Duplicate,
+    /// Further behind the high-water than the reorder window passed to
+    /// [`SeqTracker::classify_arrival`]; never returned by
+    /// [`classify`](SeqTracker::classify).
+    TooOld,
+}
+
+/// Remembers the highest sequence number seen and classifies new arrivals
+/// against it, without keeping a bitmap like [`SeqWindow`](crate::SeqWindow).
+#[derive(Clone, Debug, Default)]
+pub struct SeqTracker<T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    high: Option<SequenceInt<T, BITS, P>>,
+}
+
+impl<T, const BITS: u8, P> SeqTracker<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    pub fn new() -> Self {
+        Self { high: None }
+    }
+
+    /// Highest sequence number seen so far, if any.
+    #[must_use]
+    pub fn high_water(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.high
+    }
+
+    /// Classifies `seq`, moving the high-water to it when it is newer.
+    pub fn classify(&mut self, seq: SequenceInt<T, BITS, P>) -> Arrival {
+        match self.high {
+            Some(high) if seq == high => Arrival::Duplicate,
+            Some(high) if seq < high => Arrival::Reorder,
+            _ => {
+                self.high = Some(seq);
+                Arrival::InOrder
+            }
+        }
+    }
+
+    /// Like [`classify`](Self::classify), but a `seq` more than `window`
+    /// steps behind the high-water is [`TooOld`](Arrival::TooOld) rather than
+    /// a [`Reorder`](Arrival::Reorder).
+    ///
+    /// Only an in-order `seq` moves the high-water; a reorder is behind it by
+    /// definition, so leaves it where it is.
+    pub fn classify_arrival(&mut self, seq: SequenceInt<T, BITS, P>, window: T) -> Arrival {
+        match self.classify(seq) {
+            Arrival::Reorder
+                if self
+                    .high
+                    .is_some_and(|high| seq.forward_distance(high) > window) =>
+            {
+                Arrival::TooOld
+            }
+            arrival => arrival,
+        }
+    }
+
+    /// Moves the high-water to `seq` if it is newer, returning whether it
+    /// did. Same as `classify(seq) == Arrival::InOrder`.
+    pub fn update_max(&mut self, seq: SequenceInt<T, BITS, P>) -> bool {
+        self.classify(seq) == Arrival::InOrder
+    }
+}
+
+#[test]
+fn test_seq_tracker() {
+    use crate::SeqU16;
+    use Arrival::*;
+
+    let mut t = SeqTracker::<u16, 16>::new();
+    assert_eq!(t.high_water(), None);
+    for v in [65534, 65535, 0, 1, 5] {
+        assert_eq!(t.classify(SeqU16::from(v)), InOrder);
+    }
+    assert_eq!(t.high_water(), Some(SeqU16::from(5)));
+
+    assert_eq!(t.classify(SeqU16::from(3)), Reorder);
+    assert_eq!(t.classify(SeqU16::from(65535)), Reorder);
+    assert_eq!(t.high_water(), Some(SeqU16::from(5)));
+
+    assert_eq!(t.classify(SeqU16::from(5)), Duplicate);
+    assert_eq!(t.classify(SeqU16::from(6)), InOrder);
+    assert_eq!(t.classify(SeqU16::from(6)), Duplicate);
+}
+
+#[test]
+fn test_update_max() {
+    use crate::SeqU16;
+
+    let mut t = SeqTracker::<u16, 16>::new();
+    assert!(t.update_max(SeqU16::from(65535)));
+    assert!(t.update_max(SeqU16::from(2)));
+    assert!(!t.update_max(SeqU16::from(2)));
+    assert!(!t.update_max(SeqU16::from(65535)));
+    assert_eq!(t.high_water(), Some(SeqU16::from(2)));
+}
+
+#[test]
+fn test_classify_arrival() {
+    use crate::SeqU16;
+    use Arrival::*;
+
+    let mut t = SeqTracker::<u16, 16>::new();
+    assert_eq!(t.classify_arrival(SeqU16::from(65533), 4), InOrder);
+    assert_eq!(t.classify_arrival(SeqU16::from(2), 4), InOrder);
+    assert_eq!(t.classify_arrival(SeqU16::from(2), 4), Duplicate);
+    // 4 behind, across the wrap
+    assert_eq!(t.classify_arrival(SeqU16::from(65534), 4), Reorder);
+    assert_eq!(t.classify_arrival(SeqU16::from(65533), 4), TooOld);
+    assert_eq!(t.classify_arrival(SeqU16::from(1), 0), TooOld);
+    assert_eq!(t.high_water(), Some(SeqU16::from(2)));
+
+    // a gap is still in order
+    assert_eq!(t.classify_arrival(SeqU16::from(40), 4), InOrder);
+    assert_eq!(t.classify_arrival(SeqU16::from(2), 4), TooOld);
+    assert_eq!(t.classify_arrival(SeqU16::from(36), 4), Reorder);
+    assert_eq!(t.high_water(), Some(SeqU16::from(40)));
+}
+
+#[test]
+fn test_tracker_tie_break() {
+    use crate::LessWins;
+
+    // 128 is exactly half the space away from 0, so the policy decides
+    let mut greater = SeqTracker::<u8, 8>::new();
+    assert!(greater.update_max(SequenceInt::from(0)));
+    assert!(greater.update_max(SequenceInt::from(128)));
+
+    let mut less = SeqTracker::<u8, 8, LessWins>::new();
+    assert!(less.update_max(SequenceInt::from(0)));
+    assert!(!less.update_max(SequenceInt::from(128)));
+    assert_eq!(less.high_water(), Some(SequenceInt::from(0)));
+}
@@ -0,0 +1,473 @@
+use crate::{GreaterWins, SequenceInt, TieBreak, UInt};
+
+/// Outcome of checking a sequence number against a [`SeqWindow`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WindowResult {
+    /// Not seen before and within (or ahead of) the window.
+    Accepted,
+    /// Already seen.
+    Duplicate,
+    /// Behind the window, so it can't be told apart from a replay.
+    TooOld,
+}
+
+/// Anti-replay window (as in IPsec/DTLS) remembering which of the last `size`
+/// sequence numbers up to the high-water have been seen.
+///
+/// The window is a `u128` bitmap where bit `i` stands for `high - i`, so moving
+/// the high-water forward by `k` is a single shift. Windows hold at most
+/// [`MAX_SIZE`](Self::MAX_SIZE) entries, and should stay well below half the
+/// sequence space so "behind" and "ahead" remain unambiguous.
+#[derive(Clone, Debug)]
+pub struct SeqWindow<T, const BITS: u8, P = GreaterWins>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    high: Option<SequenceInt<T, BITS, P>>,
+    bitmap: u128,
+    size: u32,
+}
+
+impl<T, const BITS: u8, P> SeqWindow<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    pub const MAX_SIZE: u32 = u128::BITS;
+
+    /// Creates an empty window tracking `size` sequence numbers.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero or larger than [`MAX_SIZE`](Self::MAX_SIZE).
+    pub fn new(size: u32) -> Self {
+        match Self::with_capacity(size) {
+            Some(window) => window,
+            None => panic!("window size must be in 1..={}, got {size}", Self::MAX_SIZE),
+        }
+    }
+
+    /// Creates an empty window tracking `size` sequence numbers, or `None` if
+    /// `size` is zero or larger than [`MAX_SIZE`](Self::MAX_SIZE).
+    #[must_use]
+    pub fn with_capacity(size: u32) -> Option<Self> {
+        (1..=Self::MAX_SIZE).contains(&size).then_some(Self {
+            high: None,
+            bitmap: 0,
+            size,
+        })
+    }
+
+    #[must_use]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Highest sequence number accepted so far, if any.
+    #[must_use]
+    pub fn high_water(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.high
+    }
+
+    fn size_mask(&self) -> u128 {
+        u128::MAX >> (u128::BITS - self.size)
+    }
+
+    /// Classifies `seq` without recording it.
+    #[must_use]
+    pub fn check(&self, seq: SequenceInt<T, BITS, P>) -> WindowResult {
+        let Some(high) = self.high else {
+            return WindowResult::Accepted;
+        };
+        let dist = high.distance(seq);
+        if dist > 0 {
+            return WindowResult::Accepted;
+        }
+        let behind = dist.unsigned_abs();
+        if behind >= self.size as u64 {
+            WindowResult::TooOld
+        } else if self.bitmap & (1 << behind) != 0 {
+            WindowResult::Duplicate
+        } else {
+            WindowResult::Accepted
+        }
+    }
+
+    /// Classifies `seq` and, if accepted, records it, advancing the
+    /// high-water when `seq` is ahead of it.
+    pub fn check_and_update(&mut self, seq: SequenceInt<T, BITS, P>) -> WindowResult {
+        let result = self.check(seq);
+        if result != WindowResult::Accepted {
+            return result;
+        }
+        let Some(high) = self.high else {
+            self.high = Some(seq);
+            self.bitmap = 1;
+            return result;
+        };
+        let dist = high.distance(seq);
+        if dist > 0 {
+            let ahead = dist as u64;
+            self.bitmap = if ahead >= self.size as u64 {
+                1
+            } else {
+                ((self.bitmap << ahead) | 1) & self.size_mask()
+            };
+            self.high = Some(seq);
+        } else {
+            self.bitmap |= 1 << dist.unsigned_abs();
+        }
+        result
+    }
+
+    /// Runs [`check_and_update`](Self::check_and_update) on each of `seqs` in
+    /// order, writing the results to the matching slots of `out`.
+    ///
+    /// # Panics
+    ///
+    /// If `out` and `seqs` have different lengths.
+    pub fn check_and_update_many(
+        &mut self,
+        seqs: &[SequenceInt<T, BITS, P>],
+        out: &mut [WindowResult],
+    ) {
+        assert_eq!(
+            seqs.len(),
+            out.len(),
+            "output slice length must match input"
+        );
+        for (&seq, result) in seqs.iter().zip(out) {
+            *result = self.check_and_update(seq);
+        }
+    }
+}
+
+/// A 64-entry window, the size RFC 4303 recommends for IPsec, with nothing
+/// seen yet.
+impl<T, const BITS: u8, P> Default for SeqWindow<T, BITS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+/// [`SeqWindow`] with its size fixed at compile time, for any `WINDOW`, in a
+/// bitmap of `WORDS` `u64`s.
+///
+/// `WORDS` must be `WINDOW.div_ceil(64)`. It is a separate parameter only as
+/// a workaround: stable Rust can't yet size an array by an expression over
+/// another const generic, so the caller spells out what `WINDOW` implies. A
+/// mismatch fails the build, and the aliases [`FixedSeqWindow64`],
+/// [`FixedSeqWindow128`], [`FixedSeqWindow256`] and [`FixedSeqWindow1024`]
+/// fill it in for the common sizes.
+///
+/// Bit `i` of the bitmap (word `i / 64`) stands for `high - i`.
+#[derive(Clone, Debug)]
+pub struct FixedSeqWindow<
+    T,
+    const BITS: u8,
+    const WINDOW: usize,
+    const WORDS: usize,
+    P = GreaterWins,
+> where
+    T: UInt,
+    P: TieBreak,
+{
+    high: Option<SequenceInt<T, BITS, P>>,
+    bitmap: [u64; WORDS],
+}
+
+/// [`FixedSeqWindow`] of 64 entries, the size RFC 4303 recommends for IPsec.
+pub type FixedSeqWindow64<T, const BITS: u8, P = GreaterWins> = FixedSeqWindow<T, BITS, 64, 1, P>;
+/// [`FixedSeqWindow`] of 128 entries.
+pub type FixedSeqWindow128<T, const BITS: u8, P = GreaterWins> = FixedSeqWindow<T, BITS, 128, 2, P>;
+/// [`FixedSeqWindow`] of 256 entries.
+pub type FixedSeqWindow256<T, const BITS: u8, P = GreaterWins> = FixedSeqWindow<T, BITS, 256, 4, P>;
+/// [`FixedSeqWindow`] of 1024 entries, for high-rate links where 64 is too
+/// small to absorb reordering.
+pub type FixedSeqWindow1024<T, const BITS: u8, P = GreaterWins> =
+    FixedSeqWindow<T, BITS, 1024, 16, P>;
+
+impl<T, const BITS: u8, const WINDOW: usize, const WORDS: usize, P>
+    FixedSeqWindow<T, BITS, WINDOW, WORDS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    /// Creates an empty window.
+    pub fn new() -> Self {
+        const {
+            assert!(WINDOW > 0, "window size must be at least 1");
+            assert!(
+                WORDS == WINDOW.div_ceil(64),
+                "WORDS must be WINDOW.div_ceil(64)"
+            );
+        }
+        Self {
+            high: None,
+            bitmap: [0; WORDS],
+        }
+    }
+
+    /// Highest sequence number accepted so far, if any.
+    #[must_use]
+    pub fn high_water(&self) -> Option<SequenceInt<T, BITS, P>> {
+        self.high
+    }
+
+    fn is_set(&self, behind: usize) -> bool {
+        self.bitmap[behind / 64] & (1 << (behind % 64)) != 0
+    }
+
+    fn set(&mut self, behind: usize) {
+        self.bitmap[behind / 64] |= 1 << (behind % 64);
+    }
+
+    // Moves every bit `by` places toward the old end, dropping those that
+    // fall out of the window.
+    fn shift(&mut self, by: usize) {
+        if by >= WINDOW {
+            self.bitmap = [0; WORDS];
+            return;
+        }
+        let (words, bits) = (by / 64, by % 64);
+        for w in (0..WORDS).rev() {
+            let mut word = 0;
+            if w >= words {
+                word = self.bitmap[w - words] << bits;
+                if bits > 0 && w > words {
+                    word |= self.bitmap[w - words - 1] >> (64 - bits);
+                }
+            }
+            self.bitmap[w] = word;
+        }
+        if !WINDOW.is_multiple_of(64) {
+            self.bitmap[WORDS - 1] &= u64::MAX >> (64 - WINDOW % 64);
+        }
+    }
+
+    /// Classifies `seq` without recording it.
+    #[must_use]
+    pub fn check(&self, seq: SequenceInt<T, BITS, P>) -> WindowResult {
+        let Some(high) = self.high else {
+            return WindowResult::Accepted;
+        };
+        let dist = high.distance(seq);
+        if dist > 0 {
+            return WindowResult::Accepted;
+        }
+        let behind = dist.unsigned_abs();
+        if behind >= WINDOW as u64 {
+            WindowResult::TooOld
+        } else if self.is_set(behind as usize) {
+            WindowResult::Duplicate
+        } else {
+            WindowResult::Accepted
+        }
+    }
+
+    /// Classifies `seq` and, if accepted, records it, advancing the
+    /// high-water when `seq` is ahead of it.
+    pub fn check_and_update(&mut self, seq: SequenceInt<T, BITS, P>) -> WindowResult {
+        let result = self.check(seq);
+        if result != WindowResult::Accepted {
+            return result;
+        }
+        let dist = self.high.map_or(0, |high| high.distance(seq));
+        if self.high.is_none() || dist > 0 {
+            self.shift(usize::try_from(dist).unwrap_or(usize::MAX));
+            self.high = Some(seq);
+            self.set(0);
+        } else {
+            self.set(dist.unsigned_abs() as usize);
+        }
+        result
+    }
+}
+
+impl<T, const BITS: u8, const WINDOW: usize, const WORDS: usize, P> Default
+    for FixedSeqWindow<T, BITS, WINDOW, WORDS, P>
+where
+    T: UInt,
+    P: TieBreak,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test_seq_window() {
+    use crate::SeqU16;
+    use WindowResult::*;
+
+    let mut w = SeqWindow::<u16, 16>::new(64);
+    assert_eq!(w.check_and_update(SeqU16::from(65530)), Accepted);
+    assert_eq!(w.check_and_update(SeqU16::from(65530)), Duplicate);
+    assert_eq!(w.check_and_update(SeqU16::from(65528)), Accepted);
+    assert_eq!(w.check_and_update(SeqU16::from(65528)), Duplicate);
+
+    // advance across the wrap, keeping the older bits
+    assert_eq!(w.check_and_update(SeqU16::from(3)), Accepted);
+    assert_eq!(w.high_water(), Some(SeqU16::from(3)));
+    assert_eq!(w.check(SeqU16::from(65530)), Duplicate);
+    assert_eq!(w.check(SeqU16::from(65528)), Duplicate);
+    assert_eq!(w.check(SeqU16::from(65529)), Accepted);
+    assert_eq!(w.check_and_update(SeqU16::from(0)), Accepted);
+    assert_eq!(w.check_and_update(SeqU16::from(0)), Duplicate);
+
+    // 3 - 64 is the oldest slot still in the window
+    assert_eq!(w.check(SeqU16::from(3u16.wrapping_sub(63))), Accepted);
+    assert_eq!(w.check(SeqU16::from(3u16.wrapping_sub(64))), TooOld);
+
+    // a burst past the whole window forgets everything behind it
+    assert_eq!(w.check_and_update(SeqU16::from(200)), Accepted);
+    assert_eq!(w.check(SeqU16::from(3)), TooOld);
+    assert_eq!(w.check(SeqU16::from(199)), Accepted);
+    assert_eq!(w.check(SeqU16::from(200)), Duplicate);
+}
+
+#[test]
+fn test_seq_window_full_width_bitmap() {
+    use crate::SeqU32;
+    use WindowResult::*;
+
+    let mut w = SeqWindow::<u32, 32>::new(128);
+    for s in (0..128u32).step_by(2) {
+        assert_eq!(w.check_and_update(SeqU32::from(s)), Accepted);
+    }
+    // advancing by 127 leaves only 126 in the window, in its oldest slot
+    assert_eq!(w.check_and_update(SeqU32::from(253)), Accepted);
+    assert_eq!(w.check(SeqU32::from(126)), Duplicate);
+    assert_eq!(w.check(SeqU32::from(127)), Accepted);
+    assert_eq!(w.check(SeqU32::from(125)), TooOld);
+    assert_eq!(w.check_and_update(SeqU32::from(127)), Accepted);
+    assert_eq!(w.check(SeqU32::from(127)), Duplicate);
+}
+
+#[test]
+fn test_check_and_update_many() {
+    use crate::SeqU16;
+
+    let raw = [
+        65500u16, 65501, 65499, 3, 65501, 65530, 2, 3, 65300, 100, 40, 65535, 99, 36, 37,
+    ];
+    let seqs = raw.map(SeqU16::from);
+
+    let mut single = SeqWindow::<u16, 16>::new(64);
+    let expected = seqs.map(|s| single.check_and_update(s));
+
+    let mut batch = SeqWindow::<u16, 16>::new(64);
+    let mut out = [WindowResult::TooOld; 15];
+    batch.check_and_update_many(&seqs, &mut out);
+    assert_eq!(out, expected);
+    assert_eq!(batch.high_water(), single.high_water());
+    assert!(out.contains(&WindowResult::Duplicate));
+    assert!(out.contains(&WindowResult::TooOld));
+
+    // a second batch continues from the same state
+    let mut out = [WindowResult::Accepted; 2];
+    batch.check_and_update_many(&seqs[..2], &mut out);
+    assert_eq!(out, [WindowResult::TooOld; 2]);
+}
+
+#[test]
+fn test_window_construction() {
+    use crate::SeqU32;
+
+    let mut window = SeqWindow::<u32, 32>::default();
+    assert_eq!(window.size(), 64);
+    assert_eq!(window.high_water(), None);
+    assert_eq!(
+        window.check_and_update(SeqU32::from(100)),
+        WindowResult::Accepted
+    );
+    assert_eq!(window.check(SeqU32::from(37)), WindowResult::Accepted);
+    assert_eq!(window.check(SeqU32::from(36)), WindowResult::TooOld);
+
+    assert_eq!(
+        SeqWindow::<u32, 32>::with_capacity(1).map(|w| w.size()),
+        Some(1)
+    );
+    assert_eq!(
+        SeqWindow::<u32, 32>::with_capacity(128).map(|w| w.size()),
+        Some(128)
+    );
+    assert!(SeqWindow::<u32, 32>::with_capacity(129).is_none());
+    assert!(SeqWindow::<u32, 32>::with_capacity(0).is_none());
+}
+
+#[test]
+fn test_fixed_seq_window() {
+    use crate::SeqU32;
+    use WindowResult::*;
+
+    let mut w = FixedSeqWindow128::<u32, 32>::new();
+    assert_eq!(w.check_and_update(SeqU32::from(u32::MAX - 10)), Accepted);
+    for s in (0..128u32).step_by(3) {
+        assert_eq!(w.check_and_update(SeqU32::from(s)), Accepted);
+        assert_eq!(w.check_and_update(SeqU32::from(s)), Duplicate);
+    }
+    assert_eq!(w.high_water(), Some(SeqU32::from(126)));
+    // behind the high-water, on both sides of the word boundary
+    assert_eq!(w.check(SeqU32::from(63)), Duplicate);
+    assert_eq!(w.check(SeqU32::from(60)), Duplicate);
+    assert_eq!(w.check(SeqU32::from(61)), Accepted);
+    assert_eq!(w.check(SeqU32::from(u32::MAX)), Accepted);
+    assert_eq!(w.check(SeqU32::from(u32::MAX - 10)), TooOld);
+
+    // a small step keeps bits that cross into the second word
+    assert_eq!(w.check_and_update(SeqU32::from(200)), Accepted);
+    assert_eq!(w.check(SeqU32::from(126)), Duplicate);
+    assert_eq!(w.check(SeqU32::from(75)), Duplicate);
+    assert_eq!(w.check(SeqU32::from(73)), Accepted);
+    assert_eq!(w.check(SeqU32::from(72)), TooOld);
+    assert_eq!(w.check_and_update(SeqU32::from(74)), Accepted);
+
+    // a burst past the whole window forgets everything behind it
+    assert_eq!(w.check_and_update(SeqU32::from(500)), Accepted);
+    assert_eq!(w.check(SeqU32::from(200)), TooOld);
+    assert_eq!(w.check(SeqU32::from(499)), Accepted);
+    assert_eq!(w.check(SeqU32::from(373)), Accepted);
+    assert_eq!(w.check(SeqU32::from(372)), TooOld);
+}
+
+#[test]
+fn test_fixed_seq_window_aliases() {
+    use crate::SeqU32;
+    use WindowResult::*;
+
+    // holds exactly `WINDOW` entries behind the high-water
+    fn check<const WINDOW: usize, const WORDS: usize>(
+        mut w: FixedSeqWindow<u32, 32, WINDOW, WORDS>,
+    ) {
+        let high = SeqU32::from(5000);
+        assert_eq!(w.check_and_update(high), Accepted);
+        assert_eq!(w.check(high - (WINDOW as u32 - 1)), Accepted);
+        assert_eq!(w.check(high - WINDOW as u32), TooOld);
+    }
+
+    check(FixedSeqWindow64::<u32, 32>::new());
+    check(FixedSeqWindow128::<u32, 32>::new());
+    check(FixedSeqWindow256::<u32, 32>::new());
+    check(FixedSeqWindow1024::<u32, 32>::new());
+}
+
+#[test]
+fn test_fixed_seq_window_matches_dynamic() {
+    use crate::SeqU16;
+
+    let mut state = 0xb17_u64;
+    let mut fixed = FixedSeqWindow::<u16, 16, 100, 2>::default();
+    let mut dynamic = SeqWindow::<u16, 16>::new(100);
+    let mut seq = SeqU16::from(65000);
+    for _ in 0..5000 {
+        let step = (crate::next_rand(&mut state) % 300) as i64 - 150;
+        seq += crate::SeqDelta::new(step);
+        assert_eq!(fixed.check_and_update(seq), dynamic.check_and_update(seq));
+    }
+}
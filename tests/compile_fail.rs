@@ -0,0 +1,8 @@
+// The width guards are post-monomorphization errors, which `cargo check` never
+// reaches; trybuild only does a full build when there are passing cases too.
+#[test]
+fn test_width_guard() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,57 @@
+//! Exercises the optional integrations together, to catch features that
+//! interact badly. Run with `--features full`.
+#![cfg(feature = "full")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use seqnum::{LessWins, SeqRange, SeqU16, SeqU24, SeqU64, SequenceInt, SmallSeqRangeSet};
+
+// xorshift64, good enough to fill the fuzz input without pulling in a dep
+fn bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+fn round_trip<'a, S>(u: &mut Unstructured<'a>)
+where
+    S: Arbitrary<'a> + serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let value = S::arbitrary(u).unwrap();
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<S>(&json).unwrap(), value, "{json}");
+}
+
+#[test]
+fn test_arbitrary_serde_round_trip() {
+    let data = bytes(0x5eed, 64 * 1024);
+    let mut u = Unstructured::new(&data);
+    for _ in 0..1000 {
+        round_trip::<SeqU16>(&mut u);
+        round_trip::<SeqU24>(&mut u);
+        round_trip::<SeqU64>(&mut u);
+        round_trip::<SequenceInt<u32, 14>>(&mut u);
+        round_trip::<SequenceInt<u8, 3, LessWins>>(&mut u);
+        round_trip::<Vec<SequenceInt<u16, 10>>>(&mut u);
+    }
+}
+
+#[test]
+fn test_arbitrary_into_small_range_set() {
+    let data = bytes(0xfeed, 4096);
+    let mut u = Unstructured::new(&data);
+    let mut set = SmallSeqRangeSet::<u16, 16, 4>::default();
+    for _ in 0..200 {
+        let start = SeqU16::arbitrary(&mut u).unwrap();
+        let len = u8::arbitrary(&mut u).unwrap() % 16;
+        set.insert_range(SeqRange::new(start, start + len as u16));
+    }
+    for seq in set.iter() {
+        assert!(set.contains(seq));
+    }
+}
@@ -0,0 +1,5 @@
+use seqnum::SequenceInt;
+
+fn main() {
+    let _ = SequenceInt::<u8, 16>::from(3);
+}
@@ -0,0 +1,5 @@
+use seqnum::SeqU24;
+
+fn main() {
+    let _: [u8; 4] = SeqU24::from(1).to_be_bytes();
+}
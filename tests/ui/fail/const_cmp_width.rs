@@ -0,0 +1,5 @@
+use seqnum::SequenceInt;
+
+fn main() {
+    let _ = SequenceInt::<u8, 9>::new_unchecked(0).const_cmp(SequenceInt::new_unchecked(1));
+}
@@ -0,0 +1,5 @@
+use seqnum::SequenceInt;
+
+fn main() {
+    let _ = SequenceInt::<u32, 0>::from(0);
+}
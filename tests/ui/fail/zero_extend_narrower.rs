@@ -0,0 +1,5 @@
+use seqnum::SequenceInt;
+
+fn main() {
+    let _ = SequenceInt::<u32, 24>::from(3).zero_extend::<14>();
+}
@@ -0,0 +1,8 @@
+use seqnum::{SeqU8, SeqU64, SequenceInt};
+
+fn main() {
+    let _ = SeqU8::from(3);
+    let _ = SeqU64::from(3);
+    let _ = SequenceInt::<u32, 1>::from(1);
+    let _ = SequenceInt::<u16, 14>::from(3);
+}